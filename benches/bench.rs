@@ -14,15 +14,15 @@ fn c() -> Criterion {
 fn git_hash() -> String {
     use std::process::Command;
     let output = Command::new("git")
-        .args(&["rev-parse", "--short", "HEAD"])
+        .args(["rev-parse", "--short", "HEAD"])
         .output()
         .unwrap();
     String::from(String::from_utf8(output.stdout).unwrap().trim())
 }
 
 mod louds {
-    use criterion::{BatchSize, Criterion};
-    use louds_rs::{Louds, LoudsIndex, LoudsNodeNum};
+    use criterion::{BatchSize, BenchmarkId, Criterion};
+    use louds_rs::{Louds, LoudsBuilder, LoudsIndex, LoudsNodeNum};
 
     const NS: [u64; 5] = [1 << 11, 1 << 12, 1 << 13, 1 << 14, 1 << 15];
 
@@ -40,9 +40,7 @@ mod louds {
         }
 
         // Leaves
-        for _ in (n_nodes / 2 + 1)..=(n_nodes) {
-            bits.push(false);
-        }
+        bits.resize(bits.len() + (n_nodes - n_nodes / 2) as usize, false);
 
         bits
     }
@@ -57,13 +55,14 @@ mod louds {
     pub fn from_bits_benchmark(_: &mut Criterion) {
         let times = 10;
 
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Louds::from::<&[bool]>(&[...(bin tree of N nodes)]) {} times",
-                super::git_hash(),
-                times,
-            ),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Louds::from::<&[bool]>(&[...(bin tree of N nodes)]) {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
                 b.iter_batched(
                     || generate_binary_tree_lbs_bits(n - 1),
                     |bits| {
@@ -73,21 +72,22 @@ mod louds {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn from_str_benchmark(_: &mut Criterion) {
         let times = 10;
 
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Louds::from::<&str>(\"...(bin tree of N nodes)\") {} times",
-                super::git_hash(),
-                times,
-            ),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Louds::from::<&str>(\"...(bin tree of N nodes)\") {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
                 b.iter_batched(
                     || generate_binary_tree_lbs_string(n - 1),
                     |s| {
@@ -97,21 +97,51 @@ mod louds {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
+    }
+
+    pub fn builder_benchmark(_: &mut Criterion) {
+        let times = 10;
+
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] LoudsBuilder::push_node() for (bin tree of N nodes) {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
+                b.iter(|| {
+                    for _ in 0..times {
+                        let n_nodes = n - 1;
+                        let mut builder = LoudsBuilder::with_capacity(n_nodes);
+                        for _ in 1..=(n_nodes / 2) {
+                            builder.push_node(2).unwrap();
+                        }
+                        for _ in (n_nodes / 2 + 1)..=n_nodes {
+                            builder.push_node(0).unwrap();
+                        }
+                        let _ = builder.finish().unwrap();
+                    }
+                })
+            });
+        }
+        group.finish();
     }
 
     pub fn node_num_to_index_benchmark(_: &mut Criterion) {
         let times = 10_000;
 
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Louds(N)::node_num_to_index() {} times",
-                super::git_hash(),
-                times,
-            ),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Louds(N)::node_num_to_index() {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
                 b.iter_batched(
                     || {
                         let bits = generate_binary_tree_lbs_bits(n - 1);
@@ -126,21 +156,22 @@ mod louds {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn index_to_node_num_benchmark(_: &mut Criterion) {
         let times = 10_000;
 
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Louds(N)::index_to_node_num() {} times",
-                super::git_hash(),
-                times,
-            ),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Louds(N)::index_to_node_num() {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
                 b.iter_batched(
                     || {
                         let bits = generate_binary_tree_lbs_bits(n - 1);
@@ -155,21 +186,22 @@ mod louds {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn parent_to_children_benchmark(_: &mut Criterion) {
         let times = 10_000;
 
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Louds(N)::parent_to_children() {} times",
-                super::git_hash(),
-                times,
-            ),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Louds(N)::parent_to_children() {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
                 b.iter_batched(
                     || {
                         let bits = generate_binary_tree_lbs_bits(n - 1);
@@ -184,21 +216,22 @@ mod louds {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn child_to_parent_benchmark(_: &mut Criterion) {
         let times = 10_000;
 
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Louds(N)::child_to_parent() {} times",
-                super::git_hash(),
-                times,
-            ),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Louds(N)::child_to_parent() {} times",
+            super::git_hash(),
+            times,
+        ));
+        for n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
                 b.iter_batched(
                     || {
                         let bits = generate_binary_tree_lbs_bits(n - 1);
@@ -213,9 +246,62 @@ mod louds {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
+    }
+}
+
+mod louds_trie {
+    use criterion::{BenchmarkId, Criterion};
+    use louds_rs::{LoudsNodeNum, LoudsTrie};
+
+    const FANOUTS: [u16; 4] = [4, 16, 64, 256];
+
+    /// All 2-byte keys over `0..fanout`, so that root and its children have `fanout` children each.
+    fn generate_keys(fanout: u16) -> Vec<[u8; 2]> {
+        (0..fanout)
+            .flat_map(|a| (0..fanout).map(move |b| [a as u8, b as u8]))
+            .collect()
+    }
+
+    /// Lookup by linear iteration over `ChildNodeIter`, as `LoudsTrie` users did before sibling label search.
+    fn contains_by_linear_iteration(trie: &LoudsTrie<u8>, key: &[u8]) -> bool {
+        key.iter()
+            .try_fold(LoudsNodeNum(1), |node_num, label| {
+                trie.louds()
+                    .parent_to_children_nodes(node_num)
+                    .find(|&child| trie.label(child) == Some(label))
+            })
+            .is_some_and(|node_num| trie.is_terminal(node_num))
+    }
+
+    pub fn contains_benchmark(_: &mut Criterion) {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] LoudsTrie::contains() for all (2-byte keys with fanout N)",
+            super::git_hash(),
+        ));
+        for fanout in FANOUTS.iter() {
+            let keys = generate_keys(*fanout);
+            let trie = LoudsTrie::from_sorted_keys(&keys).unwrap();
+            group.bench_with_input(
+                BenchmarkId::new("sibling label search", fanout),
+                &keys,
+                |b, keys| b.iter(|| keys.iter().all(|key| trie.contains(key))),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("ChildNodeIter linear iteration", fanout),
+                &keys,
+                |b, keys| {
+                    b.iter(|| {
+                        keys.iter()
+                            .all(|key| contains_by_linear_iteration(&trie, key))
+                    })
+                },
+            );
+        }
+        group.finish();
     }
 }
 
@@ -223,9 +309,11 @@ criterion_group!(
     benches,
     louds::from_bits_benchmark,
     louds::from_str_benchmark,
+    louds::builder_benchmark,
     louds::node_num_to_index_benchmark,
     louds::index_to_node_num_benchmark,
     louds::parent_to_children_benchmark,
     louds::child_to_parent_benchmark,
+    louds_trie::contains_benchmark,
 );
 criterion_main!(benches);
@@ -0,0 +1,65 @@
+mod automaton_impl;
+
+/// Automaton run over labels of keys by [LoudsTrie::search()](struct.LoudsTrie.html#method.search).
+///
+/// The trie feeds labels from root downward and stops descending once [can_match()](#method.can_match) is `false`, so dead states prune whole subtrees.
+pub trait Automaton<L> {
+    /// State of the automaton. It is cloned per visited node.
+    type State: Clone;
+
+    /// Returns the state before any label.
+    fn start(&self) -> Self::State;
+
+    /// Returns the state after `label` from `state`.
+    fn accept(&self, state: &Self::State, label: &L) -> Self::State;
+
+    /// Returns whether the labels fed so far are accepted.
+    fn is_match(&self, state: &Self::State) -> bool;
+
+    /// Returns whether some (possibly empty) continuation from `state` can be accepted. `false` means `state` is dead.
+    fn can_match(&self, _state: &Self::State) -> bool {
+        true
+    }
+}
+
+/// Automaton accepting keys which start with a prefix.
+#[derive(Clone, Debug)]
+pub struct Prefix<'p, L> {
+    prefix: &'p [L],
+}
+
+/// Automaton accepting keys which contain labels of a query in order, not necessarily contiguously (e.g. "ace" is a subsequence of "abcde").
+#[derive(Clone, Debug)]
+pub struct Subsequence<'s, L> {
+    query: &'s [L],
+}
+
+/// Automaton accepting keys which match a glob pattern as a whole.
+///
+/// [Glob::new()](#method.new) parses a pattern over bytes:
+///
+/// - `*` matches any sequence of labels (including empty).
+/// - `?` matches any single label.
+/// - `[abc]`, `[a-z]` match a label in the class, while `[!a-z]` (or `[^a-z]`) matches a label not in it.
+/// - `\` escapes the next character.
+/// - Any other character matches itself.
+#[derive(Clone, Debug)]
+pub struct Glob<L> {
+    tokens: Vec<GlobToken<L>>,
+}
+
+/// Token of [Glob](struct.Glob.html).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum GlobToken<L> {
+    /// Matches the label.
+    Label(L),
+
+    /// Matches any single label (`?`).
+    Any,
+
+    /// Matches any sequence of labels (`*`).
+    AnySequence,
+
+    /// Matches a label in one of inclusive `ranges`, or not in any of them if `negated` (`[...]`).
+    Class { ranges: Vec<(L, L)>, negated: bool },
+}
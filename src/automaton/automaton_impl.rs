@@ -0,0 +1,292 @@
+use super::{Automaton, Glob, GlobToken, Prefix, Subsequence};
+use crate::LoudsError;
+
+impl<L, A: Automaton<L> + ?Sized> Automaton<L> for &A {
+    type State = A::State;
+
+    fn start(&self) -> Self::State {
+        (**self).start()
+    }
+
+    fn accept(&self, state: &Self::State, label: &L) -> Self::State {
+        (**self).accept(state, label)
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        (**self).is_match(state)
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        (**self).can_match(state)
+    }
+}
+
+impl<'p, L> Prefix<'p, L> {
+    /// Creates an automaton matching keys which start with `prefix`.
+    pub fn new(prefix: &'p [L]) -> Self {
+        Self { prefix }
+    }
+}
+
+impl<'p, L: Eq> Automaton<L> for Prefix<'p, L> {
+    /// Number of labels of the prefix matched so far, or `None` after a mismatch.
+    type State = Option<usize>;
+
+    fn start(&self) -> Self::State {
+        Some(0)
+    }
+
+    fn accept(&self, state: &Self::State, label: &L) -> Self::State {
+        match *state {
+            Some(n) if n == self.prefix.len() => Some(n),
+            Some(n) if self.prefix[n] == *label => Some(n + 1),
+            _ => None,
+        }
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        *state == Some(self.prefix.len())
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        state.is_some()
+    }
+}
+
+impl<'s, L> Subsequence<'s, L> {
+    /// Creates an automaton matching keys which contain `query` as a (not necessarily contiguous) subsequence.
+    pub fn new(query: &'s [L]) -> Self {
+        Self { query }
+    }
+}
+
+impl<'s, L: Eq> Automaton<L> for Subsequence<'s, L> {
+    /// Number of labels of the query matched so far.
+    type State = usize;
+
+    fn start(&self) -> Self::State {
+        0
+    }
+
+    fn accept(&self, state: &Self::State, label: &L) -> Self::State {
+        match self.query.get(*state) {
+            Some(expected) if expected == label => state + 1,
+            _ => *state,
+        }
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        *state == self.query.len()
+    }
+}
+
+impl Glob<u8> {
+    /// Parses a glob `pattern` over bytes. See [Glob](struct.Glob.html) for the syntax.
+    ///
+    /// # Errors
+    /// [LoudsError::InvalidPattern](enum.LoudsError.html#variant.InvalidPattern): `pattern` has an unclosed `[` or a trailing `\`.
+    pub fn new(pattern: &str) -> Result<Self, LoudsError> {
+        let bytes = pattern.as_bytes();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let token = match bytes[i] {
+                b'*' => GlobToken::AnySequence,
+                b'?' => GlobToken::Any,
+                b'\\' => {
+                    i += 1;
+                    let &b = bytes
+                        .get(i)
+                        .ok_or(LoudsError::InvalidPattern { position: i - 1 })?;
+                    GlobToken::Label(b)
+                }
+                b'[' => {
+                    let start = i;
+                    i += 1;
+                    let negated = matches!(bytes.get(i), Some(b'!') | Some(b'^'));
+                    if negated {
+                        i += 1;
+                    }
+                    let mut ranges = Vec::new();
+                    // `]` right after `[` (or `[!`) is a member, not the end.
+                    let mut first = true;
+                    loop {
+                        match bytes.get(i) {
+                            None => return Err(LoudsError::InvalidPattern { position: start }),
+                            Some(b']') if !first => break,
+                            Some(&lo) => match (bytes.get(i + 1), bytes.get(i + 2)) {
+                                (Some(b'-'), Some(&hi)) if hi != b']' => {
+                                    ranges.push((lo, hi));
+                                    i += 3;
+                                }
+                                _ => {
+                                    ranges.push((lo, lo));
+                                    i += 1;
+                                }
+                            },
+                        }
+                        first = false;
+                    }
+                    GlobToken::Class { ranges, negated }
+                }
+                b => GlobToken::Label(b),
+            };
+            tokens.push(token);
+            i += 1;
+        }
+        Ok(Self { tokens })
+    }
+}
+
+impl<L> Glob<L> {
+    /// Builds a glob from tokens, for labels other than bytes.
+    pub fn from_tokens(tokens: Vec<GlobToken<L>>) -> Self {
+        Self { tokens }
+    }
+
+    /// Adds `position` and, skipping `*` which may match empty, the positions after it to `positions` kept sorted.
+    fn add_position(&self, positions: &mut Vec<usize>, mut position: usize) {
+        loop {
+            if let Err(i) = positions.binary_search(&position) {
+                positions.insert(i, position);
+            }
+            match self.tokens.get(position) {
+                Some(GlobToken::AnySequence) => position += 1,
+                _ => return,
+            }
+        }
+    }
+}
+
+impl<L: Ord> Automaton<L> for Glob<L> {
+    /// Sorted positions in the tokens which the labels fed so far can reach (NFA simulation).
+    type State = Vec<usize>;
+
+    fn start(&self) -> Self::State {
+        let mut positions = Vec::new();
+        self.add_position(&mut positions, 0);
+        positions
+    }
+
+    fn accept(&self, state: &Self::State, label: &L) -> Self::State {
+        let mut positions = Vec::new();
+        for &position in state {
+            match self.tokens.get(position) {
+                Some(GlobToken::Label(expected)) if expected == label => {
+                    self.add_position(&mut positions, position + 1)
+                }
+                Some(GlobToken::Any) => self.add_position(&mut positions, position + 1),
+                Some(GlobToken::AnySequence) => self.add_position(&mut positions, position),
+                Some(GlobToken::Class { ranges, negated }) => {
+                    let in_class = ranges.iter().any(|(lo, hi)| lo <= label && label <= hi);
+                    if in_class != *negated {
+                        self.add_position(&mut positions, position + 1)
+                    }
+                }
+                _ => {}
+            }
+        }
+        positions
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        state.last() == Some(&self.tokens.len())
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        !state.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod glob_new_tests {
+    use super::{Glob, GlobToken};
+    use crate::LoudsError;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (pattern, expected): (&str, Result<Vec<GlobToken<u8>>, LoudsError>) = $value;
+                assert_eq!(Glob::new(pattern).map(|glob| glob.tokens), expected);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("", Ok(vec!())),
+        t2: ("a*?", Ok(vec!(GlobToken::Label(b'a'), GlobToken::AnySequence, GlobToken::Any))),
+        t3: ("\\*", Ok(vec!(GlobToken::Label(b'*')))),
+        t4: ("[a-cx]", Ok(vec!(GlobToken::Class { ranges: vec!((b'a', b'c'), (b'x', b'x')), negated: false }))),
+        t5: ("[!]a-]", Ok(vec!(GlobToken::Class { ranges: vec!((b']', b']'), (b'a', b'a'), (b'-', b'-')), negated: true }))),
+        t6: ("a[bc", Err(LoudsError::InvalidPattern { position: 1 })),
+        t7: ("a\\", Err(LoudsError::InvalidPattern { position: 1 })),
+    }
+}
+
+#[cfg(test)]
+mod automaton_tests {
+    use super::{Automaton, Glob, Prefix, Subsequence};
+
+    fn run<A: Automaton<u8>>(automaton: A, input: &str) -> bool {
+        let mut state = automaton.start();
+        for label in input.as_bytes() {
+            if !automaton.can_match(&state) {
+                return false;
+            }
+            state = automaton.accept(&state, label);
+        }
+        automaton.is_match(&state)
+    }
+
+    macro_rules! parameterized_glob_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (pattern, input, expected) = $value;
+                assert_eq!(run(&Glob::new(pattern).unwrap(), input), expected);
+            }
+        )*
+        }
+    }
+
+    parameterized_glob_tests! {
+        glob_1: ("", "", true),
+        glob_2: ("", "a", false),
+        glob_3: ("*", "", true),
+        glob_4: ("*", "abc", true),
+        glob_5: ("foo*bar", "foobar", true),
+        glob_6: ("foo*bar", "foo_bar_bar", true),
+        glob_7: ("foo*bar", "foo_baz", false),
+        glob_8: ("a?c", "abc", true),
+        glob_9: ("a?c", "ac", false),
+        glob_10: ("[a-c]*", "banana", true),
+        glob_11: ("[!a-c]*", "banana", false),
+        glob_12: ("**a**", "xax", true),
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (query, input, expected_prefix, expected_subsequence): (&str, &str, bool, bool) = $value;
+                assert_eq!(run(Prefix::new(query.as_bytes()), input), expected_prefix);
+                assert_eq!(run(Subsequence::new(query.as_bytes()), input), expected_subsequence);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("", "", true, true),
+        t2: ("", "abc", true, true),
+        t3: ("ab", "abc", true, true),
+        t4: ("ac", "abc", false, true),
+        t5: ("ca", "abc", false, false),
+        t6: ("abcd", "abc", false, false),
+    }
+}
@@ -0,0 +1,46 @@
+mod bit_vector_impl;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbg, MemSize};
+
+/// Bit vector packed into `u64` words, supporting _O(1)_ rank and _O(log N)_ select.
+///
+/// Bit `i` is `(words[i / 64] >> (i % 64)) & 1`. Unused bits of the last word are always _0_.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub(crate) struct BitVector {
+    words: Vec<u64>,
+    len: u64,
+
+    /// `block_ranks[b]` is the number of _1_ in _[0, b * BLOCK_BITS)_. Has _ceil(`words.len()` / BLOCK_WORDS) + 1_ elements.
+    block_ranks: Vec<u64>,
+
+    /// `select1_hints[k]` is the block containing the _(k * SELECT_SAMPLE + 1)_-th _1_.
+    select1_hints: Vec<u64>,
+
+    /// `select0_hints[k]` is the block containing the _(k * SELECT_SAMPLE + 1)_-th _0_.
+    select0_hints: Vec<u64>,
+}
+
+/// Appends bits into `u64` words to build [BitVector].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BitVectorBuilder {
+    words: Vec<u64>,
+    len: u64,
+}
+
+/// Borrowed [BitVector], possibly over words and directories in a serialized byte slice.
+///
+/// Rank and select are implemented here, and [BitVector] delegates to its view.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) struct BitVectorView<'a> {
+    words: &'a [u64],
+    len: u64,
+    block_ranks: &'a [u64],
+    select1_hints: &'a [u64],
+    select0_hints: &'a [u64],
+}
@@ -0,0 +1,663 @@
+use super::{BitVector, BitVectorBuilder, BitVectorView};
+use std::ops::Index;
+
+const WORD_BITS: u64 = 64;
+const BLOCK_WORDS: usize = 8;
+const BLOCK_BITS: u64 = WORD_BITS * BLOCK_WORDS as u64;
+const SELECT_SAMPLE: u64 = 4096;
+
+impl From<&[bool]> for BitVector {
+    fn from(bits: &[bool]) -> Self {
+        let mut builder = BitVectorBuilder::with_capacity(bits.len() as u64);
+        for &bit in bits {
+            builder.push(bit);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+impl From<&str> for BitVector {
+    /// '0' and '1' are bits and '_' is ignored.
+    ///
+    /// # Panics
+    /// `s` contains any character other than '0', '1', and '_'.
+    fn from(s: &str) -> Self {
+        let mut builder = BitVectorBuilder::default();
+        for c in s.chars() {
+            match c {
+                '0' => builder.push(false),
+                '1' => builder.push(true),
+                '_' => {}
+                _ => panic!("`s` must consist of '0' or '1'. '{}' included.", c),
+            }
+        }
+        builder.build()
+    }
+}
+
+static TRUE: bool = true;
+static FALSE: bool = false;
+
+impl Index<u64> for BitVector {
+    type Output = bool;
+
+    /// # Panics
+    /// When _`index` >= length of the `BitVector`_.
+    fn index(&self, index: u64) -> &Self::Output {
+        if self.get(index) {
+            &TRUE
+        } else {
+            &FALSE
+        }
+    }
+}
+
+impl<'a> Index<u64> for BitVectorView<'a> {
+    type Output = bool;
+
+    /// # Panics
+    /// When _`index` >= length of the `BitVector`_.
+    fn index(&self, index: u64) -> &Self::Output {
+        if self.get(index) {
+            &TRUE
+        } else {
+            &FALSE
+        }
+    }
+}
+
+impl BitVector {
+    /// Builds rank/select directories over `words` holding `len` bits.
+    ///
+    /// # Panics
+    /// `words` does not have exactly _ceil(`len` / 64)_ elements.
+    pub(crate) fn from_words(mut words: Vec<u64>, len: u64) -> Self {
+        assert_eq!(words.len() as u64, len.div_ceil(WORD_BITS));
+        if len % WORD_BITS != 0 {
+            let last = words.len() - 1;
+            words[last] &= (1 << (len % WORD_BITS)) - 1;
+        }
+
+        let n_blocks = words.len().div_ceil(BLOCK_WORDS);
+        let mut block_ranks = Vec::with_capacity(n_blocks + 1);
+        let mut select1_hints = Vec::new();
+        let mut select0_hints = Vec::new();
+
+        let (mut ones, mut zeros) = (0u64, 0u64);
+        block_ranks.push(0);
+        for (b, block) in words.chunks(BLOCK_WORDS).enumerate() {
+            let block_ones: u64 = block.iter().map(|w| w.count_ones() as u64).sum();
+            let block_zeros = (len - b as u64 * BLOCK_BITS).min(BLOCK_BITS) - block_ones;
+
+            while (select1_hints.len() as u64) * SELECT_SAMPLE < ones + block_ones {
+                select1_hints.push(b as u64);
+            }
+            while (select0_hints.len() as u64) * SELECT_SAMPLE < zeros + block_zeros {
+                select0_hints.push(b as u64);
+            }
+
+            ones += block_ones;
+            zeros += block_zeros;
+            block_ranks.push(ones);
+        }
+
+        Self {
+            words,
+            len,
+            block_ranks,
+            select1_hints,
+            select0_hints,
+        }
+    }
+
+    /// Same as [from_parts()](#method.from_parts) without any check, which makes a bit vector as serde could deserialize.
+    #[cfg(test)]
+    pub(crate) fn from_parts_unchecked(
+        words: Vec<u64>,
+        len: u64,
+        directories: [Vec<u64>; 3],
+    ) -> Self {
+        let [block_ranks, select1_hints, select0_hints] = directories;
+        Self {
+            words,
+            len,
+            block_ranks,
+            select1_hints,
+            select0_hints,
+        }
+    }
+
+    /// Rebuilds a bit vector from [words()](#method.words) and [directories()](#method.directories) of another one.
+    ///
+    /// Same checks as [BitVectorView::from_parts()].
+    pub(crate) fn from_parts(
+        words: Vec<u64>,
+        len: u64,
+        directories: [Vec<u64>; 3],
+    ) -> Option<Self> {
+        BitVectorView::from_parts(
+            &words,
+            len,
+            [&directories[0], &directories[1], &directories[2]],
+        )?;
+        let [block_ranks, select1_hints, select0_hints] = directories;
+        Some(Self {
+            words,
+            len,
+            block_ranks,
+            select1_hints,
+            select0_hints,
+        })
+    }
+
+    /// Returns the borrowed view, which all read operations go through.
+    pub(crate) fn view(&self) -> BitVectorView<'_> {
+        BitVectorView {
+            words: &self.words,
+            len: self.len,
+            block_ranks: &self.block_ranks,
+            select1_hints: &self.select1_hints,
+            select0_hints: &self.select0_hints,
+        }
+    }
+
+    /// Returns the words holding bits.
+    pub(crate) fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Returns the rank/select directories: block ranks, select hints for _1_ and select hints for _0_.
+    pub(crate) fn directories(&self) -> [&[u64]; 3] {
+        [&self.block_ranks, &self.select1_hints, &self.select0_hints]
+    }
+
+    /// See [BitVectorView::get()].
+    pub(crate) fn get(&self, i: u64) -> bool {
+        self.view().get(i)
+    }
+
+    /// See [BitVectorView::rank()].
+    pub(crate) fn rank(&self, i: u64) -> u64 {
+        self.view().rank(i)
+    }
+
+    /// See [BitVectorView::rank0()].
+    pub(crate) fn rank0(&self, i: u64) -> u64 {
+        self.view().rank0(i)
+    }
+
+    /// See [BitVectorView::select()].
+    pub(crate) fn select(&self, num: u64) -> Option<u64> {
+        self.view().select(num)
+    }
+
+    /// See [BitVectorView::select0()].
+    pub(crate) fn select0(&self, num: u64) -> Option<u64> {
+        self.view().select0(num)
+    }
+
+    /// Returns the number of _1_.
+    pub(crate) fn count_ones(&self) -> u64 {
+        self.view().count_ones()
+    }
+
+    /// Returns bit length.
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// See [BitVectorView::run_of_ones()].
+    pub(crate) fn run_of_ones(&self, i: u64) -> u64 {
+        self.view().run_of_ones(i)
+    }
+}
+
+impl<'a> BitVectorView<'a> {
+    /// Interprets `words` and `directories` (block ranks, select hints for _1_ and select hints for _0_) as a bit vector of `len` bits.
+    ///
+    /// Only sizes and ranges of the directories are checked, while their values are trusted.
+    /// Inconsistent values can make operations panic, so check them by [inconsistent_rank_blocks()](#method.inconsistent_rank_blocks) and [inconsistent_select_hints()](#method.inconsistent_select_hints) if untrusted.
+    /// It takes _O(<u>len</u> / 512)_ time.
+    pub(crate) fn from_parts(
+        words: &'a [u64],
+        len: u64,
+        directories: [&'a [u64]; 3],
+    ) -> Option<Self> {
+        let [block_ranks, select1_hints, select0_hints] = directories;
+        let n_blocks = words.len().div_ceil(BLOCK_WORDS);
+        if words.len() as u64 != len.div_ceil(WORD_BITS)
+            || (len % WORD_BITS != 0 && words[words.len() - 1] >> (len % WORD_BITS) != 0)
+            || block_ranks.len() != n_blocks + 1
+            || block_ranks[0] != 0
+            || block_ranks
+                .windows(2)
+                .any(|w| w[0] > w[1] || w[1] - w[0] > BLOCK_BITS)
+        {
+            return None;
+        }
+        let ones = block_ranks[n_blocks];
+        if ones > len
+            || select1_hints.len() as u64 != ones.div_ceil(SELECT_SAMPLE)
+            || select0_hints.len() as u64 != (len - ones).div_ceil(SELECT_SAMPLE)
+            || select1_hints
+                .iter()
+                .chain(select0_hints)
+                .any(|&b| b >= n_blocks as u64)
+        {
+            return None;
+        }
+        Some(Self {
+            words,
+            len,
+            block_ranks,
+            select1_hints,
+            select0_hints,
+        })
+    }
+
+    /// Returns the words holding bits.
+    pub(crate) fn words(&self) -> &'a [u64] {
+        self.words
+    }
+
+    /// Returns the rank/select directories: block ranks, select hints for _1_ and select hints for _0_.
+    pub(crate) fn directories(&self) -> [&'a [u64]; 3] {
+        [self.block_ranks, self.select1_hints, self.select0_hints]
+    }
+
+    /// Returns `i`-th bit.
+    ///
+    /// # Panics
+    /// When _`i` >= length of the `BitVector`_.
+    pub(crate) fn get(&self, i: u64) -> bool {
+        assert!(
+            i < self.len,
+            "index {} out of range of {} bits",
+            i,
+            self.len
+        );
+        (self.words[(i / WORD_BITS) as usize] >> (i % WORD_BITS)) & 1 == 1
+    }
+
+    /// Returns the number of _1_ in _[0, `i`]_.
+    ///
+    /// # Panics
+    /// When _`i` >= length of the `BitVector`_.
+    pub(crate) fn rank(&self, i: u64) -> u64 {
+        assert!(
+            i < self.len,
+            "index {} out of range of {} bits",
+            i,
+            self.len
+        );
+        self.ones_before(i + 1)
+    }
+
+    /// Returns the number of _0_ in _[0, `i`]_.
+    ///
+    /// # Panics
+    /// When _`i` >= length of the `BitVector`_.
+    pub(crate) fn rank0(&self, i: u64) -> u64 {
+        (i + 1) - self.rank(i)
+    }
+
+    /// Returns the position of the `num`-th (1-origin) _1_ if exists.
+    pub(crate) fn select(&self, num: u64) -> Option<u64> {
+        if num == 0 || num > self.count_ones() {
+            return None;
+        }
+        let b = self.find_block(num, self.select1_hints, |b| self.block_ranks[b]);
+        self.select_in_block(b, num - self.block_ranks[b], |word| word)
+    }
+
+    /// Returns the position of the `num`-th (1-origin) _0_ if exists.
+    pub(crate) fn select0(&self, num: u64) -> Option<u64> {
+        if num == 0 || num > self.len - self.count_ones() {
+            return None;
+        }
+        let zeros_before = |b: usize| b as u64 * BLOCK_BITS - self.block_ranks[b];
+        let b = self.find_block(num, self.select0_hints, zeros_before);
+        self.select_in_block(b, num - zeros_before(b), |word| !word)
+    }
+
+    /// Returns the number of _1_.
+    pub(crate) fn count_ones(&self) -> u64 {
+        *self.block_ranks.last().unwrap()
+    }
+
+    /// Returns bit length.
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns the length of the run of _1_ starting from `i`.
+    ///
+    /// It takes _O(1 + <u>length of the run</u> / 64)_ time.
+    pub(crate) fn run_of_ones(&self, i: u64) -> u64 {
+        let mut pos = i;
+        while pos < self.len {
+            let offset = pos % WORD_BITS;
+            let run = (self.words[(pos / WORD_BITS) as usize] >> offset).trailing_ones() as u64;
+            pos += run;
+            if offset + run < WORD_BITS {
+                break;
+            }
+        }
+        pos.min(self.len) - i
+    }
+
+    /// Returns an iterator over bits.
+    pub(crate) fn iter(self) -> impl Iterator<Item = bool> + 'a {
+        (0..self.len).map(move |i| self.get(i))
+    }
+
+    /// Returns blocks `b` where _`block_ranks[b + 1]` - `block_ranks[b]`_ differs from the number of _1_ in block `b`.
+    ///
+    /// It takes _O(<u>len</u> / 64)_ time.
+    pub(crate) fn inconsistent_rank_blocks(&self) -> Vec<u64> {
+        self.words
+            .chunks(BLOCK_WORDS)
+            .enumerate()
+            .filter(|&(b, block)| {
+                let ones: u64 = block.iter().map(|word| word.count_ones() as u64).sum();
+                self.block_ranks[b + 1] - self.block_ranks[b] != ones
+            })
+            .map(|(b, _)| b as u64)
+            .collect()
+    }
+
+    /// Returns `k` where `select1_hints[k]` is not the block containing the _(k * SELECT_SAMPLE + 1)_-th _1_ according to `block_ranks`, and so for `select0_hints`.
+    ///
+    /// It takes _O(<u>len</u> / SELECT_SAMPLE)_ time.
+    pub(crate) fn inconsistent_select_hints(&self) -> [Vec<u64>; 2] {
+        let zeros_before = |b: usize| b as u64 * BLOCK_BITS - self.block_ranks[b];
+        [
+            inconsistent_hints(self.select1_hints, |b| self.block_ranks[b]),
+            inconsistent_hints(self.select0_hints, zeros_before),
+        ]
+    }
+
+    /// Returns the number of _1_ in _[0, `i`)_.
+    fn ones_before(&self, i: u64) -> u64 {
+        let w = (i / WORD_BITS) as usize;
+        let b = w / BLOCK_WORDS;
+        let mut rank = self.block_ranks[b];
+        for word in &self.words[b * BLOCK_WORDS..w] {
+            rank += word.count_ones() as u64;
+        }
+        if i % WORD_BITS != 0 {
+            rank += (self.words[w] & ((1 << (i % WORD_BITS)) - 1)).count_ones() as u64;
+        }
+        rank
+    }
+
+    /// Returns the last block `b` where _`count_before(b)` < `num`_, narrowing binary search by `hints`.
+    fn find_block(&self, num: u64, hints: &[u64], count_before: impl Fn(usize) -> u64) -> usize {
+        let k = ((num - 1) / SELECT_SAMPLE) as usize;
+        let mut lo = hints[k] as usize;
+        let mut hi = hints
+            .get(k + 1)
+            .map_or(self.block_ranks.len() - 2, |&h| h as usize);
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if count_before(mid) < num {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        lo
+    }
+
+    /// Returns the position of the `num`-th (1-origin) bit counted in `to_ones(word)` from the head of block `b`.
+    fn select_in_block(&self, b: usize, mut num: u64, to_ones: impl Fn(u64) -> u64) -> Option<u64> {
+        let end = ((b + 1) * BLOCK_WORDS).min(self.words.len());
+        for w in b * BLOCK_WORDS..end {
+            let word = to_ones(self.words[w]);
+            let cnt = word.count_ones() as u64;
+            if num <= cnt {
+                return Some(w as u64 * WORD_BITS + select_in_word(word, num - 1));
+            }
+            num -= cnt;
+        }
+        None
+    }
+}
+
+/// Returns `k` where block `hints[k]` does not contain the _(k * SELECT_SAMPLE + 1)_-th bit counted by `count_before`.
+fn inconsistent_hints(hints: &[u64], count_before: impl Fn(usize) -> u64) -> Vec<u64> {
+    hints
+        .iter()
+        .enumerate()
+        .filter(|&(k, &b)| {
+            let num = k as u64 * SELECT_SAMPLE + 1;
+            !(count_before(b as usize) < num && num <= count_before(b as usize + 1))
+        })
+        .map(|(k, _)| k as u64)
+        .collect()
+}
+
+/// Returns the position of the `k`-th (0-origin) _1_ in `word`.
+fn select_in_word(word: u64, k: u64) -> u64 {
+    let mut k = k as u32;
+    let mut shift = 0;
+    loop {
+        let cnt = ((word >> shift) & 0xff).count_ones();
+        if k < cnt {
+            break;
+        }
+        k -= cnt;
+        shift += 8;
+    }
+    let mut byte = (word >> shift) & 0xff;
+    for _ in 0..k {
+        byte &= byte - 1;
+    }
+    (shift + byte.trailing_zeros()) as u64
+}
+
+impl BitVectorBuilder {
+    pub(crate) fn with_capacity(bits: u64) -> Self {
+        Self {
+            words: Vec::with_capacity(bits.div_ceil(WORD_BITS) as usize),
+            len: 0,
+        }
+    }
+
+    /// Appends a bit.
+    pub(crate) fn push(&mut self, bit: bool) {
+        if self.len % WORD_BITS == 0 {
+            self.words.push(0);
+        }
+        if bit {
+            *self.words.last_mut().unwrap() |= 1 << (self.len % WORD_BITS);
+        }
+        self.len += 1;
+    }
+
+    /// Appends `n` _1_ in _O(n / 64)_.
+    pub(crate) fn push_ones(&mut self, mut n: u64) {
+        let offset = self.len % WORD_BITS;
+        if offset != 0 && n > 0 {
+            let k = n.min(WORD_BITS - offset);
+            *self.words.last_mut().unwrap() |= (u64::MAX >> (WORD_BITS - k)) << offset;
+            self.len += k;
+            n -= k;
+        }
+        while n >= WORD_BITS {
+            self.words.push(u64::MAX);
+            self.len += WORD_BITS;
+            n -= WORD_BITS;
+        }
+        if n > 0 {
+            self.words.push((1 << n) - 1);
+            self.len += n;
+        }
+    }
+
+    /// Returns the number of bits appended.
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub(crate) fn build(self) -> BitVector {
+        BitVector::from_words(self.words, self.len)
+    }
+}
+
+#[cfg(test)]
+mod rank_select_tests {
+    use super::BitVector;
+
+    fn check(bits: &[bool]) {
+        let bv = BitVector::from(bits);
+        assert_eq!(bv.len(), bits.len() as u64);
+
+        let (mut ones, mut zeros) = (0u64, 0u64);
+        for (i, &bit) in bits.iter().enumerate() {
+            let i = i as u64;
+            assert_eq!(bv[i], bit);
+            if bit {
+                ones += 1;
+                assert_eq!(bv.select(ones), Some(i));
+            } else {
+                zeros += 1;
+                assert_eq!(bv.select0(zeros), Some(i));
+            }
+            assert_eq!(bv.rank(i), ones);
+            assert_eq!(bv.rank0(i), zeros);
+        }
+        assert_eq!(bv.select(0), None);
+        assert_eq!(bv.select(ones + 1), None);
+        assert_eq!(bv.select0(0), None);
+        assert_eq!(bv.select0(zeros + 1), None);
+
+        let mut run = 0;
+        for (i, &bit) in bits.iter().enumerate().rev() {
+            run = if bit { run + 1 } else { 0 };
+            assert_eq!(bv.run_of_ones(i as u64), run);
+        }
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (len, f): (usize, fn(usize) -> bool) = $value;
+                let bits: Vec<bool> = (0..len).map(f).collect();
+                check(&bits);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (1, |_| true),
+        t2: (1, |_| false),
+        t3: (64, |i| i % 2 == 0),
+        t4: (65, |i| i % 3 == 0),
+        t5: (512, |_| true),
+        t6: (513, |_| false),
+        t7: (10_000, |i| i % 7 < 3),
+        t8: (20_000, |i| i % 1000 == 0),
+        t9: (20_000, |i| i % 1000 != 0),
+    }
+
+    #[test]
+    fn fuzzing_test() {
+        use rand::prelude::*;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let len = rng.gen_range(1..30_000);
+            let p = rng.gen::<f64>();
+            let bits: Vec<bool> = (0..len).map(|_| rng.gen::<f64>() < p).collect();
+            check(&bits);
+        }
+    }
+}
+
+#[cfg(test)]
+mod builder_tests {
+    use super::{BitVector, BitVectorBuilder};
+
+    #[test]
+    fn push_ones() {
+        let mut expected = Vec::new();
+        let mut builder = BitVectorBuilder::default();
+        for n in [0u64, 1, 3, 63, 64, 65, 130, 2, 0, 200] {
+            builder.push_ones(n);
+            builder.push(false);
+            expected.extend(std::iter::repeat(true).take(n as usize));
+            expected.push(false);
+        }
+        assert_eq!(builder.len(), expected.len() as u64);
+        assert_eq!(builder.build(), BitVector::from(&expected[..]));
+    }
+}
+
+#[cfg(test)]
+mod from_parts_tests {
+    use super::BitVector;
+
+    fn parts(bv: &BitVector) -> (Vec<u64>, u64, [Vec<u64>; 3]) {
+        (
+            bv.words().to_vec(),
+            bv.len(),
+            bv.directories().map(|directory| directory.to_vec()),
+        )
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (len, f): (usize, fn(usize) -> bool) = $value;
+                let bits: Vec<bool> = (0..len).map(f).collect();
+                let bv = BitVector::from(&bits[..]);
+                let (words, len, directories) = parts(&bv);
+                assert_eq!(BitVector::from_parts(words, len, directories), Some(bv));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (0, |_| true),
+        t2: (1, |_| true),
+        t3: (65, |i| i % 3 == 0),
+        t4: (20_000, |i| i % 1000 == 0),
+    }
+
+    #[test]
+    fn inconsistent_parts() {
+        let bv = BitVector::from(&[true; 10_000][..]);
+        let (words, len, directories) = parts(&bv);
+
+        assert_eq!(
+            BitVector::from_parts(words.clone(), len + 64, directories.clone()),
+            None
+        );
+        // Unused bits of the last word must be 0.
+        assert_eq!(
+            BitVector::from_parts(words.clone(), len - 1, directories.clone()),
+            None
+        );
+
+        let mut broken = directories.clone();
+        broken[0].pop();
+        assert_eq!(BitVector::from_parts(words.clone(), len, broken), None);
+        let mut broken = directories.clone();
+        broken[0][1] += 513;
+        assert_eq!(BitVector::from_parts(words.clone(), len, broken), None);
+        let mut broken = directories.clone();
+        broken[1].push(0);
+        assert_eq!(BitVector::from_parts(words.clone(), len, broken), None);
+        let mut broken = directories;
+        broken[1][0] = 1000;
+        assert_eq!(BitVector::from_parts(words, len, broken), None);
+    }
+}
@@ -0,0 +1,10 @@
+mod crc32c_impl;
+
+/// Incremental CRC-32C (Castagnoli), used as the checksum of serialized data.
+///
+/// It detects all burst errors up to 32 bits, and is the same as SSE4.2 `crc32` instruction and iSCSI / ext4 / Btrfs checksums.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub(crate) struct Crc32c {
+    /// Bit-inverted CRC of the bytes so far.
+    state: u32,
+}
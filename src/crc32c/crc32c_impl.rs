@@ -0,0 +1,73 @@
+use super::Crc32c;
+
+/// Reversed Castagnoli polynomial.
+const POLY: u32 = 0x82F6_3B78;
+
+/// `TABLE[b]` is the CRC of byte `b` followed by 32 zero bits.
+const TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut b = 0;
+    while b < 256 {
+        let mut crc = b as u32;
+        let mut k = 0;
+        while k < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            k += 1;
+        }
+        table[b] = crc;
+        b += 1;
+    }
+    table
+};
+
+impl Crc32c {
+    /// Feeds `bytes` following the bytes so far.
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        let mut state = !self.state;
+        for &b in bytes {
+            state = TABLE[((state ^ b as u32) & 0xFF) as usize] ^ (state >> 8);
+        }
+        self.state = !state;
+    }
+
+    /// Returns the checksum of the bytes so far.
+    pub(crate) fn finish(&self) -> u32 {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod checksum_tests {
+    use super::Crc32c;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (bytes, expected): (&[u8], u32) = $value;
+
+                // Same checksum wherever the bytes are split.
+                for mid in 0..=bytes.len() {
+                    let mut crc = Crc32c::default();
+                    crc.update(&bytes[..mid]);
+                    crc.update(&bytes[mid..]);
+                    assert_eq!(crc.finish(), expected);
+                }
+            }
+        )*
+        }
+    }
+
+    // Check values from RFC 3720 (iSCSI), B.4.
+    parameterized_tests! {
+        t1: (b"", 0),
+        t2: (b"123456789", 0xE306_9283),
+        t3: (&[0; 32], 0x8A91_36AA),
+        t4: (&[0xFF; 32], 0x62A8_AB43),
+    }
+}
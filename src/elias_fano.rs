@@ -0,0 +1,27 @@
+mod elias_fano_impl;
+
+use crate::bit_vector::BitVector;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbg, MemSize};
+
+/// Non-decreasing sequence of `u64` in Elias-Fano encoding, taking _2 + ceil(log(U / n))_ bits per element
+/// where _U_ is the last element and _n_ is the number of elements.
+///
+/// Element `i` is split into the lower `low_bits` bits, packed in `low`, and the rest, written in unary to `high`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub(crate) struct EliasFano {
+    /// Bit _(`values[i]` >> `low_bits`) + i_ is _1_ for each `i`.
+    high: BitVector,
+
+    /// Lower `low_bits` bits of `values[i]` are at bits _[i * `low_bits`, (i + 1) * `low_bits`)_.
+    low: Vec<u64>,
+    low_bits: u32,
+
+    len: u64,
+}
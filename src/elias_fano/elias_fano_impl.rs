@@ -0,0 +1,142 @@
+use super::EliasFano;
+use crate::bit_vector::BitVectorBuilder;
+
+const WORD_BITS: u64 = 64;
+
+impl From<&[u64]> for EliasFano {
+    /// # Panics
+    /// `values` is not sorted in non-decreasing order.
+    fn from(values: &[u64]) -> Self {
+        let len = values.len() as u64;
+        let universe = values.last().copied().unwrap_or(0);
+        let low_bits = if len == 0 || universe <= len {
+            0
+        } else {
+            (universe / len).ilog2()
+        };
+
+        let mut high = BitVectorBuilder::with_capacity(len + (universe >> low_bits) + 1);
+        let mut low = vec![0u64; (len * low_bits as u64).div_ceil(WORD_BITS) as usize];
+        let mut prev = 0;
+        for (i, &value) in values.iter().enumerate() {
+            assert!(
+                prev <= value,
+                "values must be sorted in non-decreasing order"
+            );
+            prev = value;
+
+            let position = (value >> low_bits) + i as u64;
+            while high.len() < position {
+                high.push(false);
+            }
+            high.push(true);
+
+            if low_bits > 0 {
+                let low_value = value & ((1 << low_bits) - 1);
+                let bit = i as u64 * low_bits as u64;
+                let (word, offset) = ((bit / WORD_BITS) as usize, bit % WORD_BITS);
+                low[word] |= low_value << offset;
+                if offset + low_bits as u64 > WORD_BITS {
+                    low[word + 1] |= low_value >> (WORD_BITS - offset);
+                }
+            }
+        }
+
+        Self {
+            high: high.build(),
+            low,
+            low_bits,
+            len,
+        }
+    }
+}
+
+impl EliasFano {
+    /// Returns the `i`-th (0-origin) element in _O(log n)_.
+    ///
+    /// # Panics
+    /// `i` >= number of elements
+    pub(crate) fn get(&self, i: u64) -> u64 {
+        assert!(
+            i < self.len,
+            "index {} out of range for length {}",
+            i,
+            self.len
+        );
+        let high = self.high.select(i + 1).unwrap() - i;
+        (high << self.low_bits) | self.low_value(i)
+    }
+
+    fn low_value(&self, i: u64) -> u64 {
+        if self.low_bits == 0 {
+            return 0;
+        }
+        let bit = i * self.low_bits as u64;
+        let (word, offset) = ((bit / WORD_BITS) as usize, bit % WORD_BITS);
+        let mut value = self.low[word] >> offset;
+        if offset + self.low_bits as u64 > WORD_BITS {
+            value |= self.low[word + 1] << (WORD_BITS - offset);
+        }
+        value & ((1 << self.low_bits) - 1)
+    }
+}
+
+#[cfg(test)]
+mod get_tests {
+    use super::EliasFano;
+
+    fn check(values: &[u64]) {
+        let ef = EliasFano::from(values);
+        assert_eq!(ef.len, values.len() as u64);
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(ef.get(i as u64), value);
+        }
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let values: Vec<u64> = $value;
+                check(&values);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: vec!(),
+        t2: vec!(0),
+        t3: vec!(0, 0, 0),
+        t4: vec!(1, 2, 3, 4),
+        t5: vec!(0, 5, 5, 100, 1000, 1 << 20),
+        t6: vec!(u64::MAX >> 1, u64::MAX >> 1),
+        t7: (0..1000).map(|i| i * i).collect(),
+    }
+
+    #[test]
+    fn fuzzing_test() {
+        use rand::prelude::*;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let len = rng.gen_range(0..1000);
+            let max_step = 1u64 << rng.gen_range(0..40);
+            let mut value = 0;
+            let values: Vec<u64> = (0..len)
+                .map(|_| {
+                    value += rng.gen_range(0..=max_step);
+                    value
+                })
+                .collect();
+            check(&values);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsorted() {
+        let _ = EliasFano::from(&[1, 0][..]);
+    }
+}
@@ -1,4 +1,22 @@
 #![doc = include_str!("../README.md")]
 
-pub use louds::{ChildIndexIter, ChildNodeIter, Louds, LoudsIndex, LoudsNodeNum, AncestorNodeIter};
+pub use automaton::{Automaton, Glob, GlobToken, Prefix, Subsequence};
+pub use louds::{
+    AncestorNodeIter, ChildIndexIter, ChildNodeIter, LevelOrderIter, Louds, LoudsBuilder,
+    LoudsError, LoudsIndex, LoudsNodeNum, LoudsView, NodeIdMap, PostorderIter, PreorderIter,
+    VerifyReport,
+};
+pub use louds_map::{LoudsMap, LoudsMapIter};
+pub use louds_patricia_trie::{LoudsPatriciaTrie, PatriciaPredictiveSearchIter};
+pub use louds_trie::{
+    AutomatonSearchIter, CommonPrefixSearchIter, FuzzySearchIter, LoudsTrie, PredictiveSearchIter,
+    TailMode,
+};
+mod automaton;
+mod bit_vector;
+mod crc32c;
+mod elias_fano;
 mod louds;
+mod louds_map;
+mod louds_patricia_trie;
+mod louds_trie;
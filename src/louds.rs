@@ -1,7 +1,17 @@
+mod louds_ancestor;
+mod louds_builder;
+mod louds_from_tree;
 mod louds_impl;
+mod louds_io;
+mod louds_lbs;
+mod louds_level;
+mod louds_node;
+mod louds_parens;
+mod louds_traversal;
+mod louds_verify;
+mod louds_view;
 
-extern crate fid_rs;
-use fid_rs::Fid;
+use crate::bit_vector::{BitVector, BitVectorBuilder, BitVectorView};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -19,26 +29,101 @@ use mem_dbg::{MemDbg, MemSize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct Louds {
-    lbs: Fid,
+    lbs: BitVector,
+
+    /// Level-boundary index: level `d` consists of nodes _[`level_starts[d]`, `level_starts[d + 1]`)_.
+    ///
+    /// Takes one word per level. Built at construction time; see [Louds::drop_level_index()](struct.Louds.html#method.drop_level_index).
+    level_starts: Option<Vec<u64>>,
 }
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 #[repr(transparent)]
 /// Node number of [Louds](struct.Louds.html) tree
 pub struct LoudsNodeNum(pub u64);
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 #[repr(transparent)]
 /// Index of [Louds](struct.Louds.html) tree
 pub struct LoudsIndex(pub u64);
 
+/// Builder of [Louds](struct.Louds.html) taking degrees of nodes one by one in level order.
+///
+/// Bits are packed into words as they come, so no `bool` (or `char`) per bit is held while building.
+///
+/// # Examples
+/// ```
+/// use louds_rs::{LoudsBuilder, LoudsIndex, LoudsNodeNum};
+///
+/// // Same tree as `Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0")`.
+/// let mut builder = LoudsBuilder::new();
+/// for degree in [3, 1, 0, 3, 0, 0, 1, 2, 0, 0, 0] {
+///     builder.push_node(degree).unwrap();
+/// }
+/// let louds = builder.finish().unwrap();
+/// assert_eq!(louds.parent_to_children(LoudsNodeNum(8)), vec!(LoudsIndex(17), LoudsIndex(18)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LoudsBuilder {
+    lbs: BitVectorBuilder,
+
+    /// Number of nodes whose degree is already pushed.
+    n_pushed: u64,
+
+    /// Number of nodes known to exist so far: root and children of pushed nodes.
+    n_known: u64,
+}
+
+/// Borrowed [Louds](struct.Louds.html) over LBS and its rank/select directories kept elsewhere, typically in a memory-mapped file.
+///
+/// Nothing is copied or rebuilt to create it, and it supports the same navigation (children, parent and ancestors) as [Louds](struct.Louds.html), which in fact delegates to [Louds::view()](struct.Louds.html#method.view).
+///
+/// See [LoudsView::from_bytes()](struct.LoudsView.html#method.from_bytes) for how to get it from serialized data.
+#[derive(Clone, Copy, Debug)]
+pub struct LoudsView<'a> {
+    lbs: BitVectorView<'a>,
+
+    /// CRC-32C of each block of LBS words, if they are in the serialized data.
+    block_checksums: Option<&'a [u64]>,
+}
+
+/// What [Louds::verify()](struct.Louds.html#method.verify) or [LoudsView::verify()](struct.LoudsView.html#method.verify) found wrong.
+///
+/// Every field is empty (or `None`, or `false`) if nothing is wrong, which [is_ok()](#method.is_ok) tells.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct VerifyReport {
+    /// Whether the sizes of LBS and its directories do not match each other, e.g. in [Louds](struct.Louds.html) deserialized by serde. The other checks are skipped if so, since they assume the sizes.
+    pub invalid_layout: bool,
+
+    /// Blocks of LBS whose CRC-32C differs from the one in serialized data. Block `b` consists of bits _[65,536 * `b`, 65,536 * (`b` + 1))_.
+    ///
+    /// Always empty unless block checksums are in the data viewed by [LoudsView](struct.LoudsView.html).
+    pub corrupted_blocks: Vec<u64>,
+
+    /// First violation of the LBS's condition, the same as [Louds::try_from_str()](struct.Louds.html#method.try_from_str) returns.
+    pub lbs_error: Option<LoudsError>,
+
+    /// Blocks of 512 bits whose number of '1' differs from the rank directory.
+    pub inconsistent_rank_blocks: Vec<u64>,
+
+    /// `k` where the select directory for '1' does not point to the block containing the _(4,096 * `k` + 1)_-th '1' according to the rank directory.
+    pub inconsistent_select1_hints: Vec<u64>,
+
+    /// Same as [inconsistent_select1_hints](#structfield.inconsistent_select1_hints) for '0'.
+    pub inconsistent_select0_hints: Vec<u64>,
+
+    /// Whether the level-boundary index differs from LBS. It is checked only if all the other checks pass, since it needs select.
+    pub inconsistent_level_index: bool,
+}
+
 /// An index iterator
 pub struct ChildIndexIter<'a> {
-    inner: &'a Louds,
+    inner: LoudsView<'a>,
     node: LoudsNodeNum,
     start: Option<u64>,
     end: Option<u64>,
@@ -46,8 +131,138 @@ pub struct ChildIndexIter<'a> {
 /// A node iterator
 pub struct ChildNodeIter<'a>(ChildIndexIter<'a>);
 
+/// Bijection between original node ids (`0..N`) of a tree and [LoudsNodeNum](struct.LoudsNodeNum.html) of the [Louds](struct.Louds.html) built from it.
+///
+/// Returned by [Louds::from_parents()](struct.Louds.html#method.from_parents) and [Louds::from_children_lists()](struct.Louds.html#method.from_children_lists).
+/// BP and DFUDS conversions such as [Louds::to_balanced_parens()](struct.Louds.html#method.to_balanced_parens) also return it, with preorder ranks as ids.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub struct NodeIdMap {
+    /// `node_nums[id]` is the node number of `id`.
+    node_nums: Vec<LoudsNodeNum>,
+
+    /// `ids[node_num - 1]` is the original id of `node_num`.
+    ids: Vec<usize>,
+}
+
 /// An ancestor node iterator
 pub struct AncestorNodeIter<'a> {
-    inner: &'a Louds,
+    inner: LoudsView<'a>,
     node: LoudsNodeNum,
 }
+
+/// Iterator over all nodes in level order, yielding `(node, depth)`.
+///
+/// Returned by [Louds::iter_level_order()](struct.Louds.html#method.iter_level_order).
+pub struct LevelOrderIter<'a> {
+    inner: &'a Louds,
+    node: u64,
+    depth: u64,
+
+    /// First node of the next level.
+    level_end: u64,
+}
+
+/// Iterator over nodes of a (sub)tree in preorder, yielding `(node, depth)`.
+///
+/// Returned by [Louds::iter_preorder()](struct.Louds.html#method.iter_preorder) and [Louds::iter_subtree_preorder()](struct.Louds.html#method.iter_subtree_preorder).
+pub struct PreorderIter<'a> {
+    inner: &'a Louds,
+
+    /// Indices of the path from the subtree root to the last yielded node. Holds at most _<u>height</u> + 1_ elements.
+    stack: Vec<u64>,
+
+    /// Index of the subtree root until it is yielded.
+    root: Option<u64>,
+
+    /// Depth of the subtree root.
+    root_depth: u64,
+}
+
+/// Iterator over nodes of a (sub)tree in postorder, yielding `(node, depth)`.
+///
+/// Returned by [Louds::iter_postorder()](struct.Louds.html#method.iter_postorder) and [Louds::iter_subtree_postorder()](struct.Louds.html#method.iter_subtree_postorder).
+pub struct PostorderIter<'a> {
+    inner: &'a Louds,
+
+    /// Indices of the path from the subtree root to the next node to yield. Holds at most _<u>height</u> + 1_ elements.
+    stack: Vec<u64>,
+
+    /// Depth of the subtree root.
+    root_depth: u64,
+}
+
+/// Error returned by the fallible (`try_*` and `TryFrom`) API of [Louds](struct.Louds.html).
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[non_exhaustive]
+pub enum LoudsError {
+    /// LBS string contains a character other than '0', '1' and '_' at `position` (byte offset in the string).
+    InvalidCharacter { character: char, position: usize },
+
+    /// LBS does not start from "10".
+    InvalidPrefix,
+
+    /// In the range of _[0, `position`]_ of LBS, _<u>the number of '0'</u> > <u>the number of '1'</u>_ while `position` is not the last index.
+    UnbalancedPrefix { position: u64 },
+
+    /// In the whole LBS, _<u>the number of '0'</u> != <u>the number of '1'</u> + 1_.
+    Unbalanced { num_zeros: u64, num_ones: u64 },
+
+    /// Every one of `num_nodes` nodes already has its degree, so no more node can be pushed.
+    TreeComplete { num_nodes: u64 },
+
+    /// Node does not exist in this LOUDS.
+    NodeNotFound(LoudsNodeNum),
+
+    /// Index does not point to any node ('1' bit) in this LOUDS.
+    IndexNotNode(LoudsIndex),
+
+    /// node#1 is root and doesn't have parent.
+    RootHasNoParent,
+
+    /// Parent of node `id` is `parent`, which does not exist in the tree.
+    ParentNotFound { id: usize, parent: usize },
+
+    /// Node `id` has `child` as a child, which does not exist in the tree.
+    ChildNotFound { id: usize, child: usize },
+
+    /// Node `id` has more than one parent (or appears twice in its parent's children).
+    MultipleParents { id: usize },
+
+    /// Both node `first` and node `second` do not have parent.
+    MultipleRoots { first: usize, second: usize },
+
+    /// No node is root (the tree is empty or every node has a parent).
+    NoRoot,
+
+    /// Node `id` is unreachable from root because it is on (or under) a cycle.
+    Cycle { id: usize },
+
+    /// Parentheses (BP or DFUDS) have ')' without matching '(' or closing the root before the end at `position`, or `position` is the length if they are not closed.
+    UnbalancedParens { position: u64 },
+
+    /// Key at `position` is smaller than the previous key, while keys must be sorted.
+    UnsortedKeys { position: usize },
+
+    /// Key at `position` is equal to the previous key, while keys must be unique.
+    DuplicateKey { position: usize },
+
+    /// Pattern is malformed at `position` (byte offset in the pattern).
+    InvalidPattern { position: usize },
+
+    /// Serialized data does not start from the magic number of louds-rs.
+    InvalidMagic,
+
+    /// Serialized data is in format `version`, which this version of louds-rs cannot read.
+    UnsupportedVersion { version: u32 },
+
+    /// Checksum of serialized data is `actual` while `expected` is recorded.
+    ChecksumMismatch { expected: u32, actual: u32 },
+
+    /// Sections of serialized data are inconsistent with each other.
+    InvalidLayout,
+
+    /// Serialized data is not aligned to 8 bytes, so it cannot be viewed as `u64` words.
+    Misaligned,
+}
@@ -0,0 +1,131 @@
+use super::{Louds, LoudsNodeNum};
+
+impl Louds {
+    /// Returns the lowest common ancestor of `a` and `b` (which is `a` itself if `a` is an ancestor of `b`).
+    ///
+    /// Nodes are numbered in level order, so an ancestor always has a smaller number than its descendants.
+    /// Stepping up the larger one of the two, depths get equalized first and then both chains step up in lockstep until they meet.
+    ///
+    /// It takes _O(<u>depth of `a` and `b`</u> log N)_ time without allocation.
+    ///
+    /// # Panics
+    /// `a` or `b` does not exist in this LOUDS.
+    pub fn lca(&self, a: LoudsNodeNum, b: LoudsNodeNum) -> LoudsNodeNum {
+        self.lca_with_distance(a, b).0
+    }
+
+    /// Returns the number of edges on the path between `a` and `b`.
+    ///
+    /// Same time complexity as [lca()](#method.lca).
+    ///
+    /// # Panics
+    /// `a` or `b` does not exist in this LOUDS.
+    pub fn distance(&self, a: LoudsNodeNum, b: LoudsNodeNum) -> u64 {
+        self.lca_with_distance(a, b).1
+    }
+
+    /// Returns whether `a` is `b` itself or an ancestor of `b`.
+    ///
+    /// It takes _O(<u>depth of `b`</u> log N)_ time, or _O(1)_ if `a` has a larger number than `b`.
+    ///
+    /// # Panics
+    /// `a` or `b` does not exist in this LOUDS.
+    pub fn is_ancestor(&self, a: LoudsNodeNum, b: LoudsNodeNum) -> bool {
+        self.check_node_num(a).unwrap_or_else(|e| panic!("{}", e));
+        self.check_node_num(b).unwrap_or_else(|e| panic!("{}", e));
+
+        let mut b = b;
+        while b > a {
+            b = self.parent(b);
+        }
+        a == b
+    }
+
+    fn lca_with_distance(&self, a: LoudsNodeNum, b: LoudsNodeNum) -> (LoudsNodeNum, u64) {
+        self.check_node_num(a).unwrap_or_else(|e| panic!("{}", e));
+        self.check_node_num(b).unwrap_or_else(|e| panic!("{}", e));
+
+        let (mut a, mut b) = (a, b);
+        let mut distance = 0;
+        while a != b {
+            if a > b {
+                a = self.parent(a);
+            } else {
+                b = self.parent(b);
+            }
+            distance += 1;
+        }
+        (a, distance)
+    }
+
+    /// Returns the parent of non-root `node_num`.
+    fn parent(&self, node_num: LoudsNodeNum) -> LoudsNodeNum {
+        let index = self.lbs.select(node_num.0).unwrap();
+        LoudsNodeNum(self.lbs.rank0(index))
+    }
+}
+
+#[cfg(test)]
+mod lca_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, a, b, expected_lca, expected_distance) = $value;
+                let louds = Louds::from(in_s);
+                let (a, b) = (LoudsNodeNum(a), LoudsNodeNum(b));
+                assert_eq!(louds.lca(a, b), LoudsNodeNum(expected_lca));
+                assert_eq!(louds.lca(b, a), LoudsNodeNum(expected_lca));
+                assert_eq!(louds.distance(a, b), expected_distance);
+                assert_eq!(louds.distance(b, a), expected_distance);
+                assert_eq!(louds.is_ancestor(a, b), expected_lca == a.0);
+                assert_eq!(louds.is_ancestor(b, a), expected_lca == b.0);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: ("10_0", 1, 1, 1, 0),
+
+        t2_1: ("10_10_0", 1, 2, 1, 1),
+        t2_2: ("10_10_0", 2, 2, 2, 0),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 1, 11, 1, 3),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 5, 9, 1, 5),
+        t3_3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 9, 10, 4, 4),
+        t3_4: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 10, 11, 8, 2),
+        t3_5: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 6, 11, 4, 3),
+        t3_6: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 4, 9, 4, 2),
+        t3_7: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 3, 4, 1, 2),
+        t3_8: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 3, 3, 3, 0),
+    }
+}
+
+#[cfg(test)]
+mod lca_failure_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (in_s, a, b) = $value;
+                let louds = Louds::from(in_s);
+                let _ = louds.lca(LoudsNodeNum(a), LoudsNodeNum(b));
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: ("10_0", 0, 1),
+        t1_2: ("10_0", 1, 2),
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 12, 3),
+    }
+}
@@ -0,0 +1,157 @@
+use super::{BitVectorBuilder, Louds, LoudsBuilder, LoudsError, LoudsNodeNum};
+
+impl Default for LoudsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoudsBuilder {
+    /// Creates a builder with only the virtual root ("10") pushed.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a builder which can hold `n_nodes` nodes without reallocation.
+    pub fn with_capacity(n_nodes: u64) -> Self {
+        let mut lbs = BitVectorBuilder::with_capacity(n_nodes.saturating_mul(2).saturating_add(1));
+        lbs.push(true);
+        lbs.push(false);
+        Self {
+            lbs,
+            n_pushed: 0,
+            n_known: 1,
+        }
+    }
+
+    /// Pushes the degree (number of children) of the next node in level order, and returns its node number.
+    ///
+    /// It takes _O(1 + `degree` / 64)_ time.
+    ///
+    /// # Errors
+    /// [LoudsError::TreeComplete](enum.LoudsError.html#variant.TreeComplete): Every node already has its degree, i.e. the tree is complete.
+    pub fn push_node(&mut self, degree: u64) -> Result<LoudsNodeNum, LoudsError> {
+        if self.is_complete() {
+            return Err(LoudsError::TreeComplete {
+                num_nodes: self.n_pushed,
+            });
+        }
+        self.lbs.push_ones(degree);
+        self.lbs.push(false);
+        self.n_pushed += 1;
+        self.n_known += degree;
+        Ok(LoudsNodeNum(self.n_pushed))
+    }
+
+    /// Returns whether every node pushed so far (including the children declared by degrees) has its degree.
+    pub fn is_complete(&self) -> bool {
+        self.n_pushed == self.n_known
+    }
+
+    /// Builds [Louds](struct.Louds.html).
+    ///
+    /// Validation is already done in [push_node()](#method.push_node), so it only builds rank/select directories.
+    ///
+    /// # Errors
+    /// [LoudsError::Unbalanced](enum.LoudsError.html#variant.Unbalanced): Some nodes do not have their degree yet.
+    pub fn finish(self) -> Result<Louds, LoudsError> {
+        if !self.is_complete() {
+            return Err(LoudsError::Unbalanced {
+                num_zeros: self.n_pushed + 1,
+                num_ones: self.n_known,
+            });
+        }
+        Ok(Louds::new(self.lbs.build()))
+    }
+}
+
+#[cfg(test)]
+mod push_node_success_tests {
+    use crate::{Louds, LoudsBuilder, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (degrees, expected_lbs): (Vec<u64>, &str) = $value;
+                let mut builder = LoudsBuilder::new();
+                for (i, degree) in degrees.iter().enumerate() {
+                    assert_eq!(builder.push_node(*degree).unwrap(), LoudsNodeNum(i as u64 + 1));
+                }
+                let louds = builder.finish().unwrap();
+                let expected_louds = Louds::from(expected_lbs);
+                for node_num in 1..=degrees.len() as u64 {
+                    let node_num = LoudsNodeNum(node_num);
+                    assert_eq!(
+                        louds.parent_to_children(node_num),
+                        expected_louds.parent_to_children(node_num),
+                    );
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec!(0), "10_0"),
+        t2: (vec!(1, 0), "10_10_0"),
+        t3: (vec!(3, 1, 0, 3, 0, 0, 1, 2, 0, 0, 0), "10_1110_10_0_1110_0_0_10_110_0_0_0"),
+        t4: (
+            [vec!(100), vec!(0; 100)].concat(),
+            &format!("10_{}0{}", "1".repeat(100), "_0".repeat(100)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod push_node_failure_tests {
+    use crate::{LoudsBuilder, LoudsError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (degrees, expected_err): (Vec<u64>, LoudsError) = $value;
+                let mut builder = LoudsBuilder::new();
+                for degree in degrees.iter().take(degrees.len() - 1) {
+                    builder.push_node(*degree).unwrap();
+                }
+                assert_eq!(builder.push_node(*degrees.last().unwrap()), Err(expected_err));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec!(0, 0), LoudsError::TreeComplete { num_nodes: 1 }),
+        t2: (vec!(1, 0, 2), LoudsError::TreeComplete { num_nodes: 2 }),
+    }
+}
+
+#[cfg(test)]
+mod finish_failure_tests {
+    use crate::{LoudsBuilder, LoudsError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (degrees, expected_err): (Vec<u64>, LoudsError) = $value;
+                let mut builder = LoudsBuilder::new();
+                for degree in degrees {
+                    builder.push_node(degree).unwrap();
+                }
+                assert_eq!(builder.finish().unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec!(), LoudsError::Unbalanced { num_zeros: 1, num_ones: 1 }),
+        t2: (vec!(2, 0), LoudsError::Unbalanced { num_zeros: 3, num_ones: 3 }),
+    }
+}
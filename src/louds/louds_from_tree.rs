@@ -0,0 +1,361 @@
+use super::{Louds, LoudsBuilder, LoudsError, LoudsNodeNum, NodeIdMap};
+
+impl Louds {
+    /// Builds [Louds](struct.Louds.html) from a parent array.
+    ///
+    /// `parents[id]` is the parent id of node `id`, or `None` if `id` is root.
+    /// Ids do not have to be in any particular order; children of a node are placed in ascending order of their ids.
+    ///
+    /// Returns the LOUDS and the bijection between ids and [LoudsNodeNum](struct.LoudsNodeNum.html).
+    ///
+    /// It takes _O(N)_ time and _O(N)_ working space.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// //    (2)
+    /// //   /   \
+    /// // (0)   (3)
+    /// //  |
+    /// // (1)
+    /// let (louds, id_map) = Louds::from_parents(&[Some(2), Some(0), None, Some(2)]).unwrap();
+    /// assert_eq!(id_map.node_num(2), LoudsNodeNum(1));
+    /// assert_eq!(
+    ///     louds.parent_to_children_nodes(id_map.node_num(2)).map(|n| id_map.id(n)).collect::<Vec<_>>(),
+    ///     vec![0, 3],
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// - [LoudsError::ParentNotFound](enum.LoudsError.html#variant.ParentNotFound): `parents[id] >= parents.len()`.
+    /// - [LoudsError::MultipleRoots](enum.LoudsError.html#variant.MultipleRoots): More than one node has `None` as parent.
+    /// - [LoudsError::NoRoot](enum.LoudsError.html#variant.NoRoot): No node has `None` as parent.
+    /// - [LoudsError::Cycle](enum.LoudsError.html#variant.Cycle): Some nodes are unreachable from root.
+    pub fn from_parents(parents: &[Option<usize>]) -> Result<(Louds, NodeIdMap), LoudsError> {
+        let n = parents.len();
+
+        let mut root = None;
+        let mut n_children = vec![0usize; n + 1];
+        for (id, parent) in parents.iter().enumerate() {
+            match *parent {
+                None => match root {
+                    None => root = Some(id),
+                    Some(first) => {
+                        return Err(LoudsError::MultipleRoots { first, second: id });
+                    }
+                },
+                Some(parent) if parent >= n => {
+                    return Err(LoudsError::ParentNotFound { id, parent });
+                }
+                Some(parent) => n_children[parent + 1] += 1,
+            }
+        }
+        let root = root.ok_or(LoudsError::NoRoot)?;
+
+        // Children lists in CSR form: children of `id` are `children[offsets[id]..offsets[id + 1]]`, sorted by id.
+        let mut offsets = n_children;
+        for i in 1..=n {
+            offsets[i] += offsets[i - 1];
+        }
+        let mut children = vec![0usize; n - 1];
+        let mut cursors = offsets.clone();
+        for (id, parent) in parents.iter().enumerate() {
+            if let Some(parent) = *parent {
+                children[cursors[parent]] = id;
+                cursors[parent] += 1;
+            }
+        }
+
+        Self::from_csr_children(root, &offsets, &children)
+    }
+
+    /// Builds [Louds](struct.Louds.html) from children lists.
+    ///
+    /// `children_lists[id]` is the list of children ids of node `id`, whose order is kept as sibling order.
+    /// Root is the only node which does not appear in any children list.
+    ///
+    /// Returns the LOUDS and the bijection between ids and [LoudsNodeNum](struct.LoudsNodeNum.html).
+    ///
+    /// It takes _O(N)_ time and _O(N)_ working space.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// //    (2)
+    /// //   /   \
+    /// // (3)   (0)
+    /// //        |
+    /// //       (1)
+    /// let (louds, id_map) = Louds::from_children_lists(&[vec![1], vec![], vec![3, 0], vec![]]).unwrap();
+    /// assert_eq!(id_map.ids(), &[2, 3, 0, 1]);
+    ///
+    /// // Permute payloads into LOUDS order.
+    /// let payloads = ["zero", "one", "two", "three"];
+    /// let louds_payloads: Vec<_> = id_map.ids().iter().map(|&id| payloads[id]).collect();
+    /// assert_eq!(louds_payloads, vec!["two", "three", "zero", "one"]);
+    /// assert_eq!(
+    ///     louds.parent_to_children_nodes(LoudsNodeNum(1)).collect::<Vec<_>>(),
+    ///     vec![LoudsNodeNum(2), LoudsNodeNum(3)],
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// - [LoudsError::ChildNotFound](enum.LoudsError.html#variant.ChildNotFound): A child id is `>= children_lists.len()`.
+    /// - [LoudsError::MultipleParents](enum.LoudsError.html#variant.MultipleParents): A node appears more than once in children lists.
+    /// - [LoudsError::MultipleRoots](enum.LoudsError.html#variant.MultipleRoots): More than one node does not appear in children lists.
+    /// - [LoudsError::NoRoot](enum.LoudsError.html#variant.NoRoot): Every node appears in children lists.
+    /// - [LoudsError::Cycle](enum.LoudsError.html#variant.Cycle): Some nodes are unreachable from root.
+    pub fn from_children_lists<C: AsRef<[usize]>>(
+        children_lists: &[C],
+    ) -> Result<(Louds, NodeIdMap), LoudsError> {
+        let n = children_lists.len();
+
+        let mut has_parent = vec![false; n];
+        let mut offsets = Vec::with_capacity(n + 1);
+        offsets.push(0);
+        for (id, id_children) in children_lists.iter().enumerate() {
+            let id_children = id_children.as_ref();
+            for &child in id_children {
+                if child >= n {
+                    return Err(LoudsError::ChildNotFound { id, child });
+                }
+                if has_parent[child] {
+                    return Err(LoudsError::MultipleParents { id: child });
+                }
+                has_parent[child] = true;
+            }
+            offsets.push(offsets[id] + id_children.len());
+        }
+
+        let mut roots = has_parent
+            .iter()
+            .enumerate()
+            .filter(|(_, &has_parent)| !has_parent)
+            .map(|(id, _)| id);
+        let root = roots.next().ok_or(LoudsError::NoRoot)?;
+        if let Some(second) = roots.next() {
+            return Err(LoudsError::MultipleRoots {
+                first: root,
+                second,
+            });
+        }
+
+        let mut children = Vec::with_capacity(n - 1);
+        for id_children in children_lists {
+            children.extend_from_slice(id_children.as_ref());
+        }
+
+        Self::from_csr_children(root, &offsets, &children)
+    }
+
+    /// Builds [Louds](struct.Louds.html) by level-order traversal from `root` over children lists in CSR form.
+    fn from_csr_children(
+        root: usize,
+        offsets: &[usize],
+        children: &[usize],
+    ) -> Result<(Louds, NodeIdMap), LoudsError> {
+        let n = offsets.len() - 1;
+
+        let mut builder = LoudsBuilder::with_capacity(n as u64);
+
+        // `ids` doubles as BFS queue.
+        let mut ids = Vec::with_capacity(n);
+        ids.push(root);
+        let mut head = 0;
+        while head < ids.len() {
+            let id = ids[head];
+            head += 1;
+
+            let id_children = &children[offsets[id]..offsets[id + 1]];
+            ids.extend_from_slice(id_children);
+            builder.push_node(id_children.len() as u64)?;
+        }
+
+        let mut node_nums = vec![LoudsNodeNum(0); n];
+        for (i, &id) in ids.iter().enumerate() {
+            node_nums[id] = LoudsNodeNum(i as u64 + 1);
+        }
+        if ids.len() < n {
+            // Every node has at most one parent, so nodes unreachable from root are on (or under) cycles.
+            let id = node_nums
+                .iter()
+                .position(|node_num| node_num.0 == 0)
+                .unwrap();
+            return Err(LoudsError::Cycle { id });
+        }
+
+        Ok((builder.finish()?, NodeIdMap { node_nums, ids }))
+    }
+}
+
+impl NodeIdMap {
+    /// Returns the node number of `id`.
+    ///
+    /// # Panics
+    /// `id` does not exist in the original tree.
+    pub fn node_num(&self, id: usize) -> LoudsNodeNum {
+        self.node_nums[id]
+    }
+
+    /// Returns the original id of `node_num`.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in the LOUDS.
+    pub fn id(&self, node_num: LoudsNodeNum) -> usize {
+        assert!(node_num.0 > 0, "NodeNum(0) does not exist in the LOUDS");
+        self.ids[node_num.0 as usize - 1]
+    }
+
+    /// Returns the original ids in the order of node numbers (level order).
+    ///
+    /// Useful to permute per-node payloads into LOUDS order: `ids().iter().map(|&id| &payloads[id])`.
+    pub fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod from_parents_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (parents, expected_lbs, expected_ids): (&[Option<usize>], &str, Vec<usize>) = $value;
+                let (louds, id_map) = Louds::from_parents(parents).unwrap();
+                let expected_louds = Louds::from(expected_lbs);
+                for node_num in 1..=expected_ids.len() as u64 {
+                    let node_num = LoudsNodeNum(node_num);
+                    assert_eq!(
+                        louds.parent_to_children(node_num),
+                        expected_louds.parent_to_children(node_num),
+                    );
+                }
+                assert_eq!(id_map.ids(), &expected_ids[..]);
+                for (i, &id) in expected_ids.iter().enumerate() {
+                    assert_eq!(id_map.node_num(id), LoudsNodeNum(i as u64 + 1));
+                    assert_eq!(id_map.id(LoudsNodeNum(i as u64 + 1)), id);
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[None], "10_0", vec!(0)),
+        t2: (&[Some(1), None], "10_10_0", vec!(1, 0)),
+        t3: (&[Some(2), Some(0), None, Some(2)], "10_110_10_0_0", vec!(2, 0, 3, 1)),
+        t4: (
+            // Same tree as README with shuffled ids.
+            &[Some(9), Some(3), Some(9), None, Some(3), Some(10), Some(1), Some(10), Some(2), Some(3), Some(9)],
+            "10_1110_10_0_1110_0_0_10_110_0_0_0",
+            vec!(3, 1, 4, 9, 6, 0, 2, 10, 8, 5, 7),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod from_parents_failure_tests {
+    use crate::{Louds, LoudsError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (parents, expected_err): (&[Option<usize>], LoudsError) = $value;
+                assert_eq!(Louds::from_parents(parents).unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], LoudsError::NoRoot),
+        t2: (&[Some(0)], LoudsError::NoRoot),
+        t3: (&[None, None], LoudsError::MultipleRoots { first: 0, second: 1 }),
+        t4: (&[None, Some(2)], LoudsError::ParentNotFound { id: 1, parent: 2 }),
+        t5: (&[None, Some(1)], LoudsError::Cycle { id: 1 }),
+        t6: (&[None, Some(0), Some(3), Some(2)], LoudsError::Cycle { id: 2 }),
+        t7: (&[None, Some(0), Some(3), Some(4), Some(2)], LoudsError::Cycle { id: 2 }),
+    }
+}
+
+#[cfg(test)]
+mod from_children_lists_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (children_lists, expected_lbs, expected_ids): (Vec<Vec<usize>>, &str, Vec<usize>) = $value;
+                let (louds, id_map) = Louds::from_children_lists(&children_lists).unwrap();
+                let expected_louds = Louds::from(expected_lbs);
+                for node_num in 1..=expected_ids.len() as u64 {
+                    let node_num = LoudsNodeNum(node_num);
+                    assert_eq!(
+                        louds.parent_to_children(node_num),
+                        expected_louds.parent_to_children(node_num),
+                    );
+                }
+                assert_eq!(id_map.ids(), &expected_ids[..]);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec!(vec!()), "10_0", vec!(0)),
+        t2: (vec!(vec!(), vec!(0)), "10_10_0", vec!(1, 0)),
+        t3: (vec!(vec!(1), vec!(), vec!(3, 0), vec!()), "10_110_0_10_0", vec!(2, 3, 0, 1)),
+        t4: (
+            // Same tree as README with shuffled ids.
+            vec!(vec!(), vec!(6), vec!(8), vec!(1, 4, 9), vec!(), vec!(), vec!(), vec!(), vec!(), vec!(0, 2, 10), vec!(5, 7)),
+            "10_1110_10_0_1110_0_0_10_110_0_0_0",
+            vec!(3, 1, 4, 9, 6, 0, 2, 10, 8, 5, 7),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod from_children_lists_failure_tests {
+    use crate::{Louds, LoudsError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (children_lists, expected_err): (Vec<Vec<usize>>, LoudsError) = $value;
+                assert_eq!(Louds::from_children_lists(&children_lists).unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec!(), LoudsError::NoRoot),
+        t2: (vec!(vec!(0)), LoudsError::NoRoot),
+        t3: (vec!(vec!(), vec!()), LoudsError::MultipleRoots { first: 0, second: 1 }),
+        t4: (vec!(vec!(2), vec!()), LoudsError::ChildNotFound { id: 0, child: 2 }),
+        t5: (vec!(vec!(1, 1), vec!()), LoudsError::MultipleParents { id: 1 }),
+        t6: (vec!(vec!(1, 2), vec!(2), vec!()), LoudsError::MultipleParents { id: 2 }),
+        t7: (vec!(vec!(1), vec!(), vec!(3), vec!(2)), LoudsError::Cycle { id: 2 }),
+    }
+}
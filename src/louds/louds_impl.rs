@@ -1,5 +1,8 @@
-use super::{ChildIndexIter, ChildNodeIter, Louds, LoudsIndex, LoudsNodeNum, AncestorNodeIter};
-use fid_rs::Fid;
+use super::{
+    AncestorNodeIter, ChildIndexIter, ChildNodeIter, Louds, LoudsError, LoudsIndex, LoudsNodeNum,
+};
+use crate::bit_vector::{BitVector, BitVectorBuilder, BitVectorView};
+use std::fmt;
 
 impl From<&str> for Louds {
     /// Prepares for building [Louds](struct.Louds.html) from LBS (LOUDS Bit vector).
@@ -16,18 +19,10 @@ impl From<&str> for Louds {
     ///         - Each node is derived from one '1'.
     /// - In the range of _[0, <u>length of LBS</u>)_;
     ///     - _<u>the number of '0'</u> == <u>the number of '1'</u> + 1_
+    ///
+    /// Use [Louds::try_from_str()](struct.Louds.html#method.try_from_str) to get a [LoudsError](enum.LoudsError.html) instead.
     fn from(s: &str) -> Self {
-        let s: String = s
-            .chars()
-            .filter(|c| match c {
-                '0' | '1' => true,
-                '_' => false,
-                _ => panic!("not allowed"),
-            })
-            .collect();
-        let fid = Fid::from(s.as_str());
-        Self::validate_lbs(&fid);
-        Louds { lbs: fid }
+        Self::try_from_str(s).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -39,112 +34,328 @@ impl From<&[bool]> for Louds {
     /// # Panics
     /// Same as [Louds::from::<&str>()](struct.Louds.html#implementations).
     fn from(bits: &[bool]) -> Self {
-        let fid = Fid::from(bits);
-        Self::validate_lbs(&fid);
-        Louds { lbs: fid }
+        Self::try_from_bits(bits).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
 impl Louds {
+    /// Fallible version of [Louds::from::<&str>()](struct.Louds.html#impl-From%3C%26str%3E-for-Louds).
+    ///
+    /// (`TryFrom<&str>` cannot be implemented since it conflicts with the blanket implementation derived from `From<&str>`.)
+    ///
+    /// # Errors
+    /// - [LoudsError::InvalidCharacter](enum.LoudsError.html#variant.InvalidCharacter): `s` contains a character other than '0', '1' and '_'.
+    /// - Errors from [Louds::try_from_bits()](#method.try_from_bits).
+    pub fn try_from_str(s: &str) -> Result<Self, LoudsError> {
+        let mut bits = BitVectorBuilder::with_capacity(s.len() as u64);
+        for (position, character) in s.char_indices() {
+            match character {
+                '0' => bits.push(false),
+                '1' => bits.push(true),
+                '_' => {}
+                _ => {
+                    return Err(LoudsError::InvalidCharacter {
+                        character,
+                        position,
+                    })
+                }
+            }
+        }
+        Self::try_from_lbs(bits.build())
+    }
+
+    /// Fallible version of [Louds::from::<&[bool]>()](struct.Louds.html#impl-From%3C%26%5Bbool%5D%3E-for-Louds).
+    ///
+    /// # Errors
+    /// - [LoudsError::InvalidPrefix](enum.LoudsError.html#variant.InvalidPrefix): `bits` does not start from "10".
+    /// - [LoudsError::UnbalancedPrefix](enum.LoudsError.html#variant.UnbalancedPrefix): A prefix of `bits` has too many '0'.
+    /// - [LoudsError::Unbalanced](enum.LoudsError.html#variant.Unbalanced): `bits` as a whole does not have _<u>the number of '1'</u> + 1_ '0'.
+    pub fn try_from_bits(bits: &[bool]) -> Result<Self, LoudsError> {
+        Self::try_from_lbs(BitVector::from(bits))
+    }
+
+    fn try_from_lbs(lbs: BitVector) -> Result<Self, LoudsError> {
+        Self::check_lbs(lbs.view())?;
+        Ok(Self::new(lbs))
+    }
+
+    /// Builds [Louds](struct.Louds.html) over `lbs`, which must be already validated.
+    pub(super) fn new(lbs: BitVector) -> Self {
+        let mut louds = Louds {
+            lbs,
+            level_starts: None,
+        };
+        louds.build_level_index();
+        louds
+    }
+
     /// # Panics
     /// `node_num` does not exist in this LOUDS.
     pub fn node_num_to_index(&self, node_num: LoudsNodeNum) -> LoudsIndex {
-        assert!(node_num.0 > 0);
+        self.view().node_num_to_index(node_num)
+    }
 
-        let index = self
-            .lbs
-            .select(node_num.0)
-            .unwrap_or_else(|| panic!("NodeNum({}) does not exist in this LOUDS", node_num.0,));
-        LoudsIndex(index)
+    /// Fallible version of [node_num_to_index()](#method.node_num_to_index).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_node_num_to_index(&self, node_num: LoudsNodeNum) -> Result<LoudsIndex, LoudsError> {
+        self.view().try_node_num_to_index(node_num)
     }
 
     /// # Panics
     /// `index` does not point to any node in this LOUDS.
     pub fn index_to_node_num(&self, index: LoudsIndex) -> LoudsNodeNum {
-        self.validate_index(index);
+        self.view().index_to_node_num(index)
+    }
 
-        let node_num = self.lbs.rank(index.0);
-        LoudsNodeNum(node_num)
+    /// Fallible version of [index_to_node_num()](#method.index_to_node_num).
+    ///
+    /// # Errors
+    /// [LoudsError::IndexNotNode](enum.LoudsError.html#variant.IndexNotNode): `index` does not point to any node in this LOUDS.
+    pub fn try_index_to_node_num(&self, index: LoudsIndex) -> Result<LoudsNodeNum, LoudsError> {
+        self.view().try_index_to_node_num(index)
     }
 
     /// # Panics
     /// - `index` does not point to any node in this LOUDS.
     /// - `index == 0`: (node#1 is root and doesn't have parent)
     pub fn child_to_parent(&self, index: LoudsIndex) -> LoudsNodeNum {
-        self.validate_index(index);
-        assert!(index.0 != 0, "node#1 is root and doesn't have parent");
+        self.view().child_to_parent(index)
+    }
 
-        let parent_node_num = self.lbs.rank0(index.0);
-        LoudsNodeNum(parent_node_num)
+    /// Fallible version of [child_to_parent()](#method.child_to_parent).
+    ///
+    /// # Errors
+    /// - [LoudsError::IndexNotNode](enum.LoudsError.html#variant.IndexNotNode): `index` does not point to any node in this LOUDS.
+    /// - [LoudsError::RootHasNoParent](enum.LoudsError.html#variant.RootHasNoParent): `index == 0`.
+    pub fn try_child_to_parent(&self, index: LoudsIndex) -> Result<LoudsNodeNum, LoudsError> {
+        self.view().try_child_to_parent(index)
     }
 
     /// Return an iterator to the `child` and its ancestors' node numbers.
-    pub fn child_to_ancestors(&self, child: LoudsNodeNum) -> AncestorNodeIter {
-        AncestorNodeIter {
-            inner: self,
-            node: child,
-        }
+    pub fn child_to_ancestors(&self, child: LoudsNodeNum) -> AncestorNodeIter<'_> {
+        self.view().child_to_ancestors(child)
+    }
+
+    /// Fallible version of [child_to_ancestors()](#method.child_to_ancestors).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `child` does not exist in this LOUDS.
+    pub fn try_child_to_ancestors(
+        &self,
+        child: LoudsNodeNum,
+    ) -> Result<AncestorNodeIter<'_>, LoudsError> {
+        self.view().try_child_to_ancestors(child)
     }
 
     /// # Panics
     /// `node_num` does not exist in this LOUDS.
     pub fn parent_to_children(&self, node_num: LoudsNodeNum) -> Vec<LoudsIndex> {
-        self.parent_to_children_indices(node_num).collect()
+        self.view().parent_to_children(node_num)
+    }
+
+    /// Fallible version of [parent_to_children()](#method.parent_to_children).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_parent_to_children(
+        &self,
+        node_num: LoudsNodeNum,
+    ) -> Result<Vec<LoudsIndex>, LoudsError> {
+        self.view().try_parent_to_children(node_num)
     }
 
     /// # Panics
     /// `node_num` does not exist in this LOUDS.
-    pub fn parent_to_children_indices(&self, node_num: LoudsNodeNum) -> ChildIndexIter {
-        assert!(node_num.0 > 0);
+    pub fn parent_to_children_indices(&self, node_num: LoudsNodeNum) -> ChildIndexIter<'_> {
+        self.view().parent_to_children_indices(node_num)
+    }
 
-        ChildIndexIter {
-            inner: self,
-            node: node_num,
-            start: None,
-            end: None,
-        }
+    /// Fallible version of [parent_to_children_indices()](#method.parent_to_children_indices).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_parent_to_children_indices(
+        &self,
+        node_num: LoudsNodeNum,
+    ) -> Result<ChildIndexIter<'_>, LoudsError> {
+        self.view().try_parent_to_children_indices(node_num)
     }
 
     /// # Panics
     /// `node_num` does not exist in this LOUDS.
-    pub fn parent_to_children_nodes(&self, node_num: LoudsNodeNum) -> ChildNodeIter {
-        ChildNodeIter(self.parent_to_children_indices(node_num))
+    pub fn parent_to_children_nodes(&self, node_num: LoudsNodeNum) -> ChildNodeIter<'_> {
+        self.view().parent_to_children_nodes(node_num)
+    }
+
+    /// Fallible version of [parent_to_children_nodes()](#method.parent_to_children_nodes).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_parent_to_children_nodes(
+        &self,
+        node_num: LoudsNodeNum,
+    ) -> Result<ChildNodeIter<'_>, LoudsError> {
+        self.view().try_parent_to_children_nodes(node_num)
     }
 
     /// Checks if `lbs` satisfy the LBS's necessary and sufficient condition:
-    fn validate_lbs(lbs: &Fid) {
-        assert!(lbs[0]);
-        assert!(!lbs[1]);
+    ///
+    /// # Panics
+    /// `lbs` does not satisfy the condition.
+    #[cfg(test)]
+    fn validate_lbs(lbs: &BitVector) {
+        Self::check_lbs(lbs.view()).unwrap_or_else(|e| panic!("{}", e))
+    }
 
+    /// Checks if `lbs` satisfy the LBS's necessary and sufficient condition:
+    ///
+    /// It reads words one by one without rank/select directories, and bits only in words which may violate the condition.
+    pub(super) fn check_lbs(lbs: BitVectorView<'_>) -> Result<(), LoudsError> {
+        if lbs.len() < 2 || !lbs[0] || lbs[1] {
+            return Err(LoudsError::InvalidPrefix);
+        }
+
+        let last = lbs.len() - 1;
         let (mut cnt0, mut cnt1) = (0u64, 0u64);
-        for (i, bit) in lbs.iter().enumerate() {
-            if bit {
-                cnt1 += 1
+        for (k, &word) in lbs.words().iter().enumerate() {
+            let start = 64 * k as u64;
+            let n_bits = (lbs.len() - start).min(64);
+            let word = if n_bits < 64 {
+                word & ((1 << n_bits) - 1)
             } else {
-                cnt0 += 1
+                word
             };
-            assert!(
-                cnt0 <= cnt1 + 1,
-                "At index {}, the number of '0' ({}) == (the number of '1' ({})) + 2.",
-                i,
-                cnt0,
-                cnt1,
-            );
+            let word_cnt1 = word.count_ones() as u64;
+            let word_cnt0 = n_bits - word_cnt1;
+            // No prefix ending in this word has too many '0' if all of its '0' come first.
+            if cnt0 + word_cnt0 > cnt1 {
+                for j in 0..n_bits {
+                    if (word >> j) & 1 == 1 {
+                        cnt1 += 1
+                    } else {
+                        cnt0 += 1
+                    };
+                    // '0' of the last node closes the tree, so it must be the last bit.
+                    if cnt0 > cnt1 && start + j < last {
+                        return Err(LoudsError::UnbalancedPrefix {
+                            position: start + j,
+                        });
+                    }
+                }
+            } else {
+                cnt0 += word_cnt0;
+                cnt1 += word_cnt1;
+            }
         }
-        assert_eq!(cnt0, cnt1 + 1);
+        if cnt0 != cnt1 + 1 {
+            return Err(LoudsError::Unbalanced {
+                num_zeros: cnt0,
+                num_ones: cnt1,
+            });
+        }
+        Ok(())
     }
 
-    /// # Panics
-    /// `index` does not point to any node in this LOUDS.
-    fn validate_index(&self, index: LoudsIndex) {
-        assert!(
-            self.lbs[index.0],
-            "LBS[index={:?}] must be '1'",
-            index,
-        );
+    /// Checks if `node_num` exists in this LOUDS.
+    pub(super) fn check_node_num(&self, node_num: LoudsNodeNum) -> Result<(), LoudsError> {
+        self.view().check_node_num(node_num)
+    }
+}
+
+impl fmt::Display for LoudsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoudsError::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "LBS must consist of '0', '1' or '_'. '{}' included at {}.",
+                character, position,
+            ),
+            LoudsError::InvalidPrefix => write!(f, "LBS must start from \"10\"."),
+            LoudsError::UnbalancedPrefix { position } => write!(
+                f,
+                "At index {} before the last bit, the number of '0' > the number of '1'.",
+                position,
+            ),
+            LoudsError::Unbalanced {
+                num_zeros,
+                num_ones,
+            } => write!(
+                f,
+                "The number of '0' ({}) must be (the number of '1' ({})) + 1.",
+                num_zeros, num_ones,
+            ),
+            LoudsError::TreeComplete { num_nodes } => {
+                write!(f, "Every one of {} nodes already has its degree", num_nodes,)
+            }
+            LoudsError::NodeNotFound(node_num) => {
+                write!(f, "NodeNum({}) does not exist in this LOUDS", node_num.0)
+            }
+            LoudsError::IndexNotNode(index) => write!(f, "LBS[index={:?}] must be '1'", index),
+            LoudsError::RootHasNoParent => write!(f, "node#1 is root and doesn't have parent"),
+            LoudsError::ParentNotFound { id, parent } => write!(
+                f,
+                "Parent of node {} is {}, which does not exist in the tree",
+                id, parent,
+            ),
+            LoudsError::ChildNotFound { id, child } => write!(
+                f,
+                "Node {} has {} as a child, which does not exist in the tree",
+                id, child,
+            ),
+            LoudsError::MultipleParents { id } => {
+                write!(f, "Node {} has more than one parent", id)
+            }
+            LoudsError::MultipleRoots { first, second } => write!(
+                f,
+                "Both node {} and node {} do not have parent",
+                first, second,
+            ),
+            LoudsError::NoRoot => write!(f, "No node is root"),
+            LoudsError::Cycle { id } => write!(
+                f,
+                "Node {} is unreachable from root because it is on (or under) a cycle",
+                id,
+            ),
+            LoudsError::UnbalancedParens { position } => write!(
+                f,
+                "Parentheses must close the root at the end, but unbalanced at position {}",
+                position,
+            ),
+            LoudsError::UnsortedKeys { position } => write!(
+                f,
+                "Key at position {} is smaller than the previous key",
+                position,
+            ),
+            LoudsError::DuplicateKey { position } => write!(
+                f,
+                "Key at position {} is equal to the previous key",
+                position,
+            ),
+            LoudsError::InvalidPattern { position } => {
+                write!(f, "Pattern is malformed at position {}", position)
+            }
+            LoudsError::InvalidMagic => write!(f, "Data does not start from the magic number"),
+            LoudsError::UnsupportedVersion { version } => {
+                write!(f, "Format version {} is not supported", version)
+            }
+            LoudsError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Checksum is {:#010x} while {:#010x} is recorded",
+                actual, expected,
+            ),
+            LoudsError::InvalidLayout => write!(f, "Sections of data are inconsistent"),
+            LoudsError::Misaligned => write!(f, "Data must be aligned to 8 bytes"),
+        }
     }
 }
 
+impl std::error::Error for LoudsError {}
+
 impl<'a> ChildIndexIter<'a> {
     /// Return the length of the iterator.
     ///
@@ -156,7 +367,7 @@ impl<'a> ChildIndexIter<'a> {
     /// `fn len(&self) -> usize`; `&self` is not mutable:
     ///
     /// 1. Use interior mutability in [ChildIndexIter]. This was attempted with
-    /// a [std::cell::RefCell] but it hurt performance slightly.
+    ///    a [std::cell::RefCell] but it hurt performance slightly.
     ///
     /// 2. Initialize [ChildIndexIter] with the start and end. However
     ///    initializing start and end costs _O(log N)_ each.
@@ -206,6 +417,17 @@ impl<'a> Iterator for ChildIndexIter<'a> {
                 LoudsIndex(start)
             })
     }
+
+    /// Skips `n` children in _O(1)_ after the first call, which takes _O(log N)_.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.start = Some(self.end.unwrap() + 1);
+            return None;
+        }
+        self.start = Some(self.start.unwrap() + n as u64);
+        self.next()
+    }
 }
 
 impl<'a> DoubleEndedIterator for ChildIndexIter<'a> {
@@ -237,6 +459,14 @@ impl<'a> Iterator for ChildNodeIter<'a> {
             .next()
             .map(|index| self.0.inner.index_to_node_num(index))
     }
+
+    /// See [ChildIndexIter::nth].
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0
+            .nth(n)
+            .map(|index| self.0.inner.index_to_node_num(index))
+    }
 }
 
 impl<'a> Iterator for AncestorNodeIter<'a> {
@@ -277,8 +507,8 @@ impl<'a> ChildNodeIter<'a> {
 
 #[cfg(test)]
 mod validate_lbs_success_tests {
+    use crate::bit_vector::BitVector;
     use crate::Louds;
-    use fid_rs::Fid;
 
     macro_rules! parameterized_tests {
         ($($name:ident: $value:expr,)*) => {
@@ -286,8 +516,8 @@ mod validate_lbs_success_tests {
             #[test]
             fn $name() {
                 let s = $value;
-                let fid = Fid::from(s);
-                Louds::validate_lbs(&fid);
+                let lbs = BitVector::from(s);
+                Louds::validate_lbs(&lbs);
             }
         )*
         }
@@ -303,8 +533,8 @@ mod validate_lbs_success_tests {
 
 #[cfg(test)]
 mod validate_lbs_failure_tests {
+    use crate::bit_vector::BitVector;
     use crate::Louds;
-    use fid_rs::Fid;
 
     macro_rules! parameterized_tests {
         ($($name:ident: $value:expr,)*) => {
@@ -313,8 +543,8 @@ mod validate_lbs_failure_tests {
             #[should_panic]
             fn $name() {
                 let s = $value;
-                let fid = Fid::from(s);
-                Louds::validate_lbs(&fid);
+                let lbs = BitVector::from(s);
+                Louds::validate_lbs(&lbs);
             }
         )*
         }
@@ -887,6 +1117,48 @@ mod parent_to_children_indices_next_back_success_tests {
     }
 }
 
+#[cfg(test)]
+mod parent_to_children_indices_nth_success_tests {
+    use crate::{Louds, LoudsIndex, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, node_num, expected_children): (&str, u64, Vec<u64>) = $value;
+                let louds = Louds::from(in_s);
+                let node_num = LoudsNodeNum(node_num);
+                for n in 0..=expected_children.len() + 1 {
+                    let mut iter = louds.parent_to_children_indices(node_num);
+                    assert_eq!(iter.nth(n), expected_children.get(n).map(|c| LoudsIndex(*c)));
+                    assert_eq!(iter.next(), expected_children.get(n + 1).map(|c| LoudsIndex(*c)));
+
+                    let mut iter = louds.parent_to_children_nodes(node_num);
+                    assert_eq!(iter.nth(n), expected_children.get(n).map(|c| louds.index_to_node_num(LoudsIndex(*c))));
+                }
+
+                // Skipping from the middle.
+                let mut iter = louds.parent_to_children_indices(node_num);
+                if iter.next().is_some() {
+                    assert_eq!(iter.nth(1), expected_children.get(2).map(|c| LoudsIndex(*c)));
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: ("10_0", 1, vec!()),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 1, vec!(2, 3, 4)),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 2, vec!(6)),
+        t3_3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 3, vec!()),
+        t3_4: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 4, vec!(9, 10, 11)),
+        t3_8: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 8, vec!(17, 18)),
+    }
+}
+
 #[cfg(test)]
 mod parent_to_children_failure_tests {
     use crate::{Louds, LoudsNodeNum};
@@ -916,3 +1188,137 @@ mod parent_to_children_failure_tests {
         t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 12),
     }
 }
+
+#[cfg(test)]
+mod try_from_str_failure_tests {
+    use crate::{Louds, LoudsError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, expected_err) = $value;
+                let err = Louds::try_from_str(in_s).unwrap_err();
+                assert_eq!(err, expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("", LoudsError::InvalidPrefix),
+        t2: ("0", LoudsError::InvalidPrefix),
+        t3: ("01_0", LoudsError::InvalidPrefix),
+        t4: ("11_0", LoudsError::InvalidPrefix),
+        t5: ("10_0 ", LoudsError::InvalidCharacter { character: ' ', position: 4 }),
+        t6: ("10_2_0", LoudsError::InvalidCharacter { character: '2', position: 3 }),
+        t7: ("10_00", LoudsError::UnbalancedPrefix { position: 2 }),
+        t8: ("10_10_0_0", LoudsError::UnbalancedPrefix { position: 4 }),
+        t9: ("10", LoudsError::Unbalanced { num_zeros: 1, num_ones: 1 }),
+        t10: ("10_1110_10_0_1110_0_0_10_110_0_0_1", LoudsError::Unbalanced { num_zeros: 11, num_ones: 12 }),
+        t11: ("10_0_10", LoudsError::UnbalancedPrefix { position: 2 }),
+        t12: ("10_10_0_0_10", LoudsError::UnbalancedPrefix { position: 4 }),
+        t13: (&format!("10_{}0{}_10", "1".repeat(100), "_0".repeat(100)), LoudsError::UnbalancedPrefix { position: 202 }),
+    }
+}
+
+#[cfg(test)]
+mod try_from_bits_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    #[test]
+    fn same_as_from() {
+        let bits = [true, false, true, true, false, false, false];
+        let louds = Louds::try_from_bits(&bits[..]).unwrap();
+        assert_eq!(
+            louds
+                .parent_to_children_nodes(LoudsNodeNum(1))
+                .collect::<Vec<_>>(),
+            vec![LoudsNodeNum(2), LoudsNodeNum(3)],
+        );
+    }
+}
+
+#[cfg(test)]
+mod try_node_num_to_index_failure_tests {
+    use crate::{Louds, LoudsError, LoudsNodeNum};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, node_num) = $value;
+                let louds = Louds::from(in_s);
+                assert_eq!(
+                    louds.try_node_num_to_index(LoudsNodeNum(node_num)),
+                    Err(LoudsError::NodeNotFound(LoudsNodeNum(node_num))),
+                );
+                assert_eq!(
+                    louds.try_parent_to_children(LoudsNodeNum(node_num)),
+                    Err(LoudsError::NodeNotFound(LoudsNodeNum(node_num))),
+                );
+                assert!(louds.try_child_to_ancestors(LoudsNodeNum(node_num)).is_err());
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: ("10_0", 0),
+        t1_2: ("10_0", 2),
+        t1_3: ("10_0", u64::MAX),
+
+        t2_1: ("10_10_0", 0),
+        t2_2: ("10_10_0", 3),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 0),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 12),
+    }
+}
+
+#[cfg(test)]
+mod try_child_to_parent_failure_tests {
+    use crate::{Louds, LoudsError, LoudsIndex};
+
+    macro_rules! parameterized_index_not_point_to_node_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, index) = $value;
+                let louds = Louds::from(in_s);
+                assert_eq!(
+                    louds.try_index_to_node_num(LoudsIndex(index)),
+                    Err(LoudsError::IndexNotNode(LoudsIndex(index))),
+                );
+                assert_eq!(
+                    louds.try_child_to_parent(LoudsIndex(index)),
+                    Err(LoudsError::IndexNotNode(LoudsIndex(index))),
+                );
+            }
+        )*
+        }
+    }
+
+    parameterized_index_not_point_to_node_tests! {
+        t1_1: ("10_0", 1),
+        t1_2: ("10_0", 3),
+        t1_3: ("10_0", u64::MAX),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 1),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 5),
+        t3_3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 22),
+        t3_4: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 23),
+    }
+
+    #[test]
+    fn root_not_have_parent() {
+        let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+        assert_eq!(
+            louds.try_child_to_parent(LoudsIndex(0)),
+            Err(LoudsError::RootHasNoParent),
+        );
+    }
+}
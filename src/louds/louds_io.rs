@@ -0,0 +1,511 @@
+use super::{BitVector, Louds, LoudsError};
+use crate::crc32c::Crc32c;
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+
+/// Magic number at the head of serialized [Louds].
+pub(super) const MAGIC: [u8; 8] = *b"LOUDS-RS";
+
+/// Version of the format written by [Louds::write_to()].
+pub(super) const VERSION: u32 = 1;
+
+/// Flag set when rank/select directories follow the LBS.
+pub(super) const FLAG_DIRECTORIES: u32 = 1;
+
+/// Flag set when the level-boundary index follows the LBS (and the directories).
+pub(super) const FLAG_LEVEL_INDEX: u32 = 1 << 1;
+
+/// Flag set when per-block checksums of LBS follow all the other sections.
+pub(super) const FLAG_BLOCK_CHECKSUMS: u32 = 1 << 2;
+
+/// Number of LBS words covered by one block checksum.
+pub(super) const CHECKSUM_BLOCK_WORDS: usize = 1 << 10;
+
+/// Max number of words read at once, which bounds allocation by a corrupted length until data actually arrives.
+const CHUNK_WORDS: usize = 1 << 13;
+
+impl Louds {
+    /// Writes this LOUDS to `writer` in the native binary format, which later versions of louds-rs can read by [read_from()](#method.read_from).
+    ///
+    /// All integers are little-endian, and sections are in this order:
+    ///
+    /// | Size (bytes) | Content |
+    /// |--------------|---------|
+    /// | 8 | Magic number `b"LOUDS-RS"` |
+    /// | 4 | Format version (`u32`), currently _1_ |
+    /// | 4 | Flags (`u32`): bit 0 is set if rank/select directories are written, bit 1 if the level-boundary index is written, and bit 2 if block checksums are written |
+    /// | 8 | Bit length _L_ of LBS (`u64`) |
+    /// | 8 * _ceil(L / 64)_ | LBS packed into `u64` words. Bit `i` is `(words[i / 64] >> (i % 64)) & 1`, and unused bits of the last word are _0_ |
+    /// | 8 + 8 * _n_, three times | (Flag bit 0) Block ranks, select hints for '1' and select hints for '0', each as `u64` length _n_ followed by _n_ `u64` |
+    /// | 8 + 8 * _n_ | (Flag bit 1) First node number of each level, and one past the last node, as `u64` length _n_ followed by _n_ `u64` |
+    /// | 8 + 8 * _n_ | (Flag bit 2) CRC-32C of each 1,024 words (the last block may be shorter) of LBS, as `u64` length _n_ followed by _n_ `u64` |
+    /// | 4 | CRC-32C (Castagnoli) of all the preceding bytes (`u32`) |
+    ///
+    /// Directories and the level-boundary index are written so that reading checks them instead of rebuilding, and block checksums so that [LoudsView::verify()](struct.LoudsView.html#method.verify) can locate corruption.
+    /// See [write_compact_to()](#method.write_compact_to) to omit them.
+    ///
+    /// # Errors
+    /// Errors from `writer`.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+    /// let mut bytes = Vec::new();
+    /// louds.write_to(&mut bytes).unwrap();
+    ///
+    /// let read = Louds::read_from(&mut &bytes[..]).unwrap();
+    /// assert_eq!(read.parent_to_children(LoudsNodeNum(4)), louds.parent_to_children(LoudsNodeNum(4)));
+    /// ```
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        self.write_sections(writer, true)
+    }
+
+    /// Same as [write_to()](#method.write_to) except that only LBS is written (flags are _0_).
+    ///
+    /// It takes _2 + o(1)_ bits per node, while [read_from()](#method.read_from) takes _O(N)_ time to rebuild directories and the level-boundary index.
+    ///
+    /// # Errors
+    /// Errors from `writer`.
+    pub fn write_compact_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        self.write_sections(writer, false)
+    }
+
+    /// Reads a LOUDS written by [write_to()](#method.write_to) or [write_compact_to()](#method.write_compact_to) of this or earlier versions of louds-rs.
+    ///
+    /// When directories are in the data, they are checked against LBS instead of rebuilt. LBS is checked for the LBS's condition.
+    /// Both take _O(N)_ time, counting '1' a word at a time. Bits are read one by one only in words where a prefix of LBS may have too many '0', e.g. around long chains of single children.
+    /// Use [verify()](#method.verify) to check everything, e.g. for data written from memory already corrupted.
+    ///
+    /// # Errors
+    /// - Errors from `reader`. [io::ErrorKind::UnexpectedEof] if the data is truncated.
+    /// - [io::ErrorKind::InvalidData] with [LoudsError](enum.LoudsError.html) inside (which [io::Error::get_ref()] returns), if the data is not a valid LOUDS:
+    ///   - [LoudsError::InvalidMagic](enum.LoudsError.html#variant.InvalidMagic): The data does not start from the magic number.
+    ///   - [LoudsError::UnsupportedVersion](enum.LoudsError.html#variant.UnsupportedVersion): The data is written by a newer version of louds-rs.
+    ///   - [LoudsError::ChecksumMismatch](enum.LoudsError.html#variant.ChecksumMismatch): The data is corrupted.
+    ///   - [LoudsError::InvalidLayout](enum.LoudsError.html#variant.InvalidLayout): Unknown flags are set, the sizes of sections do not match, or directories or the level-boundary index are inconsistent with LBS.
+    ///   - [LoudsError::InvalidPrefix](enum.LoudsError.html#variant.InvalidPrefix), [LoudsError::UnbalancedPrefix](enum.LoudsError.html#variant.UnbalancedPrefix) and [LoudsError::Unbalanced](enum.LoudsError.html#variant.Unbalanced): LBS is invalid.
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let mut reader = ChecksumReader {
+            inner: reader,
+            crc: Crc32c::default(),
+        };
+
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid_data(LoudsError::InvalidMagic));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(invalid_data(LoudsError::UnsupportedVersion { version }));
+        }
+        let flags = read_u32(&mut reader)?;
+        if flags & !(FLAG_DIRECTORIES | FLAG_LEVEL_INDEX | FLAG_BLOCK_CHECKSUMS) != 0 {
+            return Err(invalid_data(LoudsError::InvalidLayout));
+        }
+
+        let len = read_u64(&mut reader)?;
+        let words = read_words(&mut reader, len.div_ceil(64))?;
+        let directories = if flags & FLAG_DIRECTORIES != 0 {
+            Some([
+                read_section(&mut reader)?,
+                read_section(&mut reader)?,
+                read_section(&mut reader)?,
+            ])
+        } else {
+            None
+        };
+        let level_starts = if flags & FLAG_LEVEL_INDEX != 0 {
+            Some(read_section(&mut reader)?)
+        } else {
+            None
+        };
+        // The checksum of the whole data makes block checksums redundant here.
+        if flags & FLAG_BLOCK_CHECKSUMS != 0
+            && read_section(&mut reader)?.len() != words.len().div_ceil(CHECKSUM_BLOCK_WORDS)
+        {
+            return Err(invalid_data(LoudsError::InvalidLayout));
+        }
+
+        let actual = reader.crc.finish();
+        let expected = read_u32(reader.inner)?;
+        if actual != expected {
+            return Err(invalid_data(LoudsError::ChecksumMismatch {
+                expected,
+                actual,
+            }));
+        }
+
+        let lbs = match directories {
+            Some(directories) => {
+                let lbs = BitVector::from_parts(words, len, directories)
+                    .ok_or_else(|| invalid_data(LoudsError::InvalidLayout))?;
+                // Cheap next to I/O, and inconsistent values would make navigation panic.
+                let [select1, select0] = lbs.view().inconsistent_select_hints();
+                if !lbs.view().inconsistent_rank_blocks().is_empty()
+                    || !select1.is_empty()
+                    || !select0.is_empty()
+                {
+                    return Err(invalid_data(LoudsError::InvalidLayout));
+                }
+                lbs
+            }
+            None => BitVector::from_words(words, len),
+        };
+        Louds::check_lbs(lbs.view()).map_err(invalid_data)?;
+
+        let mut louds = Louds {
+            lbs,
+            level_starts: None,
+        };
+        match level_starts {
+            // Same time as rebuilding, while a wrong index would silently make depth() wrong.
+            Some(level_starts) => {
+                if level_starts != louds.compute_level_starts() {
+                    return Err(invalid_data(LoudsError::InvalidLayout));
+                }
+                louds.level_starts = Some(level_starts);
+            }
+            None => louds.build_level_index(),
+        }
+        Ok(louds)
+    }
+
+    fn write_sections(&self, writer: &mut impl Write, with_indices: bool) -> io::Result<()> {
+        let mut writer = ChecksumWriter {
+            inner: writer,
+            crc: Crc32c::default(),
+        };
+        let level_starts = self.level_starts.as_ref().filter(|_| with_indices);
+        let mut flags = 0;
+        if with_indices {
+            flags |= FLAG_DIRECTORIES | FLAG_BLOCK_CHECKSUMS;
+        }
+        if level_starts.is_some() {
+            flags |= FLAG_LEVEL_INDEX;
+        }
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&flags.to_le_bytes())?;
+        writer.write_all(&self.lbs.len().to_le_bytes())?;
+        write_words(&mut writer, self.lbs.words())?;
+        if with_indices {
+            for directory in self.lbs.directories() {
+                write_section(&mut writer, directory)?;
+            }
+        }
+        if let Some(level_starts) = level_starts {
+            write_section(&mut writer, level_starts)?;
+        }
+        if with_indices {
+            write_section(&mut writer, &block_checksums(self.lbs.words()))?;
+        }
+
+        let checksum = writer.crc.finish();
+        writer.inner.write_all(&checksum.to_le_bytes())
+    }
+}
+
+/// Writer computing the checksum of bytes written through it.
+struct ChecksumWriter<'a, W> {
+    inner: &'a mut W,
+    crc: Crc32c,
+}
+
+impl<'a, W: Write> Write for ChecksumWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.crc.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader computing the checksum of bytes read through it.
+struct ChecksumReader<'a, R> {
+    inner: &'a mut R,
+    crc: Crc32c,
+}
+
+impl<'a, R: Read> Read for ChecksumReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.crc.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Returns CRC-32C of each [CHECKSUM_BLOCK_WORDS] words of `words`.
+pub(super) fn block_checksums(words: &[u64]) -> Vec<u64> {
+    words
+        .chunks(CHECKSUM_BLOCK_WORDS)
+        .map(|block| {
+            let mut crc = Crc32c::default();
+            for word in block {
+                crc.update(&word.to_le_bytes());
+            }
+            crc.finish() as u64
+        })
+        .collect()
+}
+
+fn invalid_data(e: LoudsError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn write_words(writer: &mut impl Write, words: &[u64]) -> io::Result<()> {
+    for chunk in words.chunks(CHUNK_WORDS) {
+        let bytes: Vec<u8> = chunk.iter().flat_map(|word| word.to_le_bytes()).collect();
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+/// Writes the length of `words` followed by `words`.
+fn write_section(writer: &mut impl Write, words: &[u64]) -> io::Result<()> {
+    writer.write_all(&(words.len() as u64).to_le_bytes())?;
+    write_words(writer, words)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads `n` words in chunks, so that memory is not allocated for more words than the data has.
+fn read_words(reader: &mut impl Read, n: u64) -> io::Result<Vec<u64>> {
+    let mut words = Vec::new();
+    let mut bytes = vec![0; 8 * n.min(CHUNK_WORDS as u64) as usize];
+    let mut remaining = n;
+    while remaining > 0 {
+        let chunk = remaining.min(CHUNK_WORDS as u64) as usize;
+        reader.read_exact(&mut bytes[..8 * chunk])?;
+        words.extend(
+            bytes[..8 * chunk]
+                .chunks_exact(8)
+                .map(|word| u64::from_le_bytes(word.try_into().unwrap())),
+        );
+        remaining -= chunk as u64;
+    }
+    Ok(words)
+}
+
+/// Reads a section written by [write_section()].
+fn read_section(reader: &mut impl Read) -> io::Result<Vec<u64>> {
+    let n = read_u64(reader)?;
+    read_words(reader, n)
+}
+
+#[cfg(test)]
+mod round_trip_tests {
+    use crate::Louds;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let lbs: &str = $value;
+                let mut louds = Louds::from(lbs);
+                let level_starts = louds.level_starts.clone();
+                for _ in 0..2 {
+                    let mut full = Vec::new();
+                    louds.write_to(&mut full).unwrap();
+                    let read = Louds::read_from(&mut &full[..]).unwrap();
+                    assert_eq!(read.lbs, louds.lbs);
+                    assert_eq!(read.level_starts, level_starts);
+
+                    // Indices are rebuilt from the compact format, and the level-boundary index is rebuilt if not written.
+                    let mut compact = Vec::new();
+                    louds.write_compact_to(&mut compact).unwrap();
+                    assert!(compact.len() <= full.len());
+                    let read = Louds::read_from(&mut &compact[..]).unwrap();
+                    assert_eq!(read.lbs, louds.lbs);
+                    assert_eq!(read.level_starts, level_starts);
+
+                    louds.drop_level_index();
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: "10_0",
+        t2: "10_10_0",
+        t3: "10_1110_10_0_1110_0_0_10_110_0_0_0",
+        t4: &format!("10_{}0{}", "1".repeat(10_000), "_0".repeat(10_000)),
+        t5: &format!("10_{}0", "10_".repeat(10_000)),
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::Crc32c;
+    use crate::Louds;
+
+    fn checksum(bytes: &[u8]) -> u32 {
+        let mut crc = Crc32c::default();
+        crc.update(bytes);
+        crc.finish()
+    }
+
+    #[test]
+    fn compact() {
+        let mut bytes = Vec::new();
+        Louds::from("10_0").write_compact_to(&mut bytes).unwrap();
+
+        let mut expected = b"LOUDS-RS".to_vec();
+        expected.extend_from_slice(&1u32.to_le_bytes()); // version
+        expected.extend_from_slice(&0u32.to_le_bytes()); // flags
+        expected.extend_from_slice(&3u64.to_le_bytes()); // bit length
+        expected.extend_from_slice(&0b001u64.to_le_bytes()); // "100"
+        let checksum = checksum(&expected);
+        expected.extend_from_slice(&checksum.to_le_bytes());
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn full() {
+        let mut bytes = Vec::new();
+        Louds::from("10_0").write_to(&mut bytes).unwrap();
+
+        let mut expected = b"LOUDS-RS".to_vec();
+        expected.extend_from_slice(&1u32.to_le_bytes()); // version
+        expected.extend_from_slice(&7u32.to_le_bytes()); // flags
+        expected.extend_from_slice(&3u64.to_le_bytes()); // bit length
+        expected.extend_from_slice(&0b001u64.to_le_bytes()); // "100"
+        let block_checksum = checksum(&0b001u64.to_le_bytes()) as u64;
+        for section in [&[0u64, 1][..], &[0], &[0], &[1, 2], &[block_checksum]] {
+            expected.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            for word in section {
+                expected.extend_from_slice(&word.to_le_bytes());
+            }
+        }
+        let checksum = checksum(&expected);
+        expected.extend_from_slice(&checksum.to_le_bytes());
+        assert_eq!(bytes, expected);
+    }
+}
+
+#[cfg(test)]
+mod read_from_failure_tests {
+    use super::Crc32c;
+    use crate::{Louds, LoudsError};
+    use std::io;
+
+    fn checksum(bytes: &[u8]) -> u32 {
+        let mut crc = Crc32c::default();
+        crc.update(bytes);
+        crc.finish()
+    }
+
+    /// Offset of flags.
+    const FLAGS: usize = 12;
+
+    /// Offset of the first word of LBS.
+    const WORDS: usize = 24;
+
+    /// Offset of the first block rank, following one word of LBS and the length of block ranks.
+    const BLOCK_RANKS: usize = WORDS + 16;
+
+    /// Size of the block checksums section (one block), which is the last one.
+    const BLOCK_CHECKSUMS: usize = 16;
+
+    /// Returns `Louds::from("10_0")` in the full format, modified by `f` with the checksum recomputed.
+    fn modified(f: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+        modified_lbs("10_0", f)
+    }
+
+    /// Same as [modified()] for `Louds::from(lbs)`.
+    fn modified_lbs(lbs: &str, f: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        Louds::from(lbs).write_to(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() - 4);
+        f(&mut bytes);
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    fn read_err(bytes: &[u8]) -> io::Error {
+        Louds::read_from(&mut &bytes[..]).unwrap_err()
+    }
+
+    macro_rules! parameterized_invalid_data_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (bytes, expected_err): (Vec<u8>, LoudsError) = $value;
+                let err = read_err(&bytes);
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+                assert_eq!(err.get_ref().unwrap().downcast_ref::<LoudsError>(), Some(&expected_err));
+            }
+        )*
+        }
+    }
+
+    parameterized_invalid_data_tests! {
+        t1: (modified(|bytes| bytes[0] = b'X'), LoudsError::InvalidMagic),
+        t2: (modified(|bytes| bytes[8] = 2), LoudsError::UnsupportedVersion { version: 2 }),
+        t3: (modified(|bytes| bytes[FLAGS] = 8), LoudsError::InvalidLayout),
+        // "110" and "101" with the block rank updated.
+        t4: (modified(|bytes| { bytes[WORDS] = 0b011; bytes[BLOCK_RANKS + 8] = 2 }), LoudsError::InvalidPrefix),
+        t5: (modified(|bytes| { bytes[WORDS] = 0b101; bytes[BLOCK_RANKS + 8] = 2 }), LoudsError::Unbalanced { num_zeros: 1, num_ones: 2 }),
+        // Block ranks start from 1.
+        t6: (modified(|bytes| bytes[BLOCK_RANKS] = 1), LoudsError::InvalidLayout),
+        // Level index says nodes are 1 and 2.
+        t7: (modified(|bytes| { let n = bytes.len(); bytes[n - 8 - BLOCK_CHECKSUMS] = 3 }), LoudsError::InvalidLayout),
+        // No block checksum while LBS has a block.
+        t8: (modified(|bytes| { let n = bytes.len(); bytes[n - BLOCK_CHECKSUMS] = 0 }), LoudsError::InvalidLayout),
+        // One more '1' in the block rank.
+        t9: (modified(|bytes| bytes[BLOCK_RANKS + 8] = 2), LoudsError::InvalidLayout),
+        // "10_10_0" turns into "10_0_10", where node 2 would be its own child.
+        t10: (modified_lbs("10_10_0", |bytes| bytes[WORDS] ^= 0b1100), LoudsError::UnbalancedPrefix { position: 2 }),
+        // Level index says levels are [1, 3) and [3, 4) instead of [1, 2) and [2, 4), while it is strictly increasing.
+        t11: (modified_lbs("10_110_0_0", |bytes| { let n = bytes.len(); bytes[n - 16 - BLOCK_CHECKSUMS] = 3 }), LoudsError::InvalidLayout),
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut bytes = Vec::new();
+        Louds::from("10_0").write_to(&mut bytes).unwrap();
+        bytes[WORDS] ^= 0b100;
+        let err = read_err(&bytes);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            err.get_ref().unwrap().downcast_ref::<LoudsError>(),
+            Some(LoudsError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut bytes = Vec::new();
+        Louds::from("10_0").write_to(&mut bytes).unwrap();
+        for len in 0..bytes.len() {
+            assert_eq!(read_err(&bytes[..len]).kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[test]
+    fn huge_length() {
+        // Allocation is bounded by the data even if the length is corrupted.
+        let bytes = modified(|bytes| bytes[WORDS - 1] = 0x7F);
+        assert_eq!(read_err(&bytes).kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,96 @@
+use super::Louds;
+use std::fmt::{self, Write};
+
+impl fmt::Display for Louds {
+    /// Writes LBS as '0' and '1', which [Louds::from::<&str>()](struct.Louds.html#implementations) accepts.
+    ///
+    /// The alternate flag (`{:#}`) puts '_' after every '0' but the last one, separating the virtual root and each node like `"10_1110_10_0_0_0"`.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::Louds;
+    ///
+    /// let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+    /// assert_eq!(format!("{}", louds), "10111010011100010110000");
+    /// assert_eq!(format!("{:#}", louds), "10_1110_10_0_1110_0_0_10_110_0_0_0");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.lbs.len() - 1;
+        for (i, bit) in self.lbs.view().iter().enumerate() {
+            if bit {
+                f.write_char('1')?;
+            } else {
+                f.write_char('0')?;
+                if f.alternate() && i as u64 != last {
+                    f.write_char('_')?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Louds {
+    /// Returns LBS as `bool`s, which [Louds::from::<&[bool]>()](struct.Louds.html#implementations) accepts.
+    ///
+    /// It takes _O(N)_ time and _2N + 1_ bytes.
+    pub fn to_bool_vec(&self) -> Vec<bool> {
+        self.lbs.view().iter().collect()
+    }
+
+    /// Returns LBS packed into `u64` words, without copying.
+    ///
+    /// LBS has _2N + 1_ bits, and bit `i` is `(words[i / 64] >> (i % 64)) & 1`. Unused bits of the last word are _0_.
+    /// This is the same as the LBS section of [write_to()](#method.write_to), in native byte order.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::Louds;
+    ///
+    /// let louds = Louds::from("10_110_0_0");
+    /// assert_eq!(louds.as_words(), &[0b0001101]);
+    /// ```
+    pub fn as_words(&self) -> &[u64] {
+        self.lbs.words()
+    }
+}
+
+#[cfg(test)]
+mod lbs_tests {
+    use crate::Louds;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, expected_words): (&str, Vec<u64>) = $value;
+                let louds = Louds::from(in_s);
+
+                assert_eq!(format!("{:#}", louds), in_s);
+                let plain = in_s.replace('_', "");
+                assert_eq!(louds.to_string(), plain);
+                assert_eq!(
+                    louds.to_bool_vec(),
+                    plain.chars().map(|c| c == '1').collect::<Vec<_>>(),
+                );
+                assert_eq!(louds.as_words(), &expected_words[..]);
+
+                // Round trips.
+                assert_eq!(Louds::from(louds.to_string().as_str()).to_string(), plain);
+                assert_eq!(Louds::from(&louds.to_bool_vec()[..]).to_string(), plain);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("10_0", vec!(0b001)),
+        t2: ("10_10_0", vec!(0b00101)),
+        t3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", vec!(0b00001101000111001011101)),
+        t4: (
+            &format!("10_{}0{}", "1".repeat(64), "_0".repeat(64)),
+            vec!(u64::MAX << 2 | 0b01, 0b11, 0),
+        ),
+    }
+}
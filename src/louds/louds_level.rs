@@ -0,0 +1,188 @@
+use super::{Louds, LoudsNodeNum};
+use std::ops::Range;
+
+impl Louds {
+    /// Returns the depth of `node_num` (root is _0_).
+    ///
+    /// It takes _O(log <u>height</u>)_ time with the level-boundary index, or _O(<u>depth</u> log N)_ time without it.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn depth(&self, node_num: LoudsNodeNum) -> u64 {
+        self.check_node_num(node_num)
+            .unwrap_or_else(|e| panic!("{}", e));
+        match &self.level_starts {
+            Some(level_starts) => {
+                level_starts.partition_point(|&start| start <= node_num.0) as u64 - 1
+            }
+            None => self.child_to_ancestors(node_num).count() as u64,
+        }
+    }
+
+    /// Returns the height of the tree, i.e. the maximum depth of nodes.
+    ///
+    /// It takes _O(1)_ time with the level-boundary index, or _O(<u>height</u> log N)_ time without it.
+    pub fn height(&self) -> u64 {
+        match &self.level_starts {
+            Some(level_starts) => level_starts.len() as u64 - 2,
+            // The last node in level order is one of the deepest.
+            None => self.depth(LoudsNodeNum(self.num_nodes())),
+        }
+    }
+
+    /// Returns the range of node numbers at depth `d`. Nodes are numbered in level order, so a level is contiguous.
+    ///
+    /// Returns an empty range if _`d` > [height()](#method.height)_.
+    ///
+    /// It takes _O(1)_ time with the level-boundary index, or _O(`d` log N)_ time without it.
+    pub fn level_range(&self, d: u64) -> Range<LoudsNodeNum> {
+        let end_of_tree = LoudsNodeNum(self.num_nodes() + 1);
+        match &self.level_starts {
+            Some(level_starts) if d < level_starts.len() as u64 - 1 => {
+                LoudsNodeNum(level_starts[d as usize])..LoudsNodeNum(level_starts[d as usize + 1])
+            }
+            Some(_) => end_of_tree..end_of_tree,
+            None => {
+                let mut levels = self.levels();
+                for _ in 0..d {
+                    if levels.next().is_none() {
+                        return end_of_tree..end_of_tree;
+                    }
+                }
+                levels.next().unwrap_or(end_of_tree..end_of_tree)
+            }
+        }
+    }
+
+    /// Returns the number of nodes at depth `d`.
+    ///
+    /// Same time complexity as [level_range()](#method.level_range).
+    pub fn level_width(&self, d: u64) -> u64 {
+        let range = self.level_range(d);
+        range.end.0 - range.start.0
+    }
+
+    /// Builds the level-boundary index, which takes one word per level.
+    ///
+    /// Constructors call it, so it is only needed after [drop_level_index()](#method.drop_level_index).
+    /// It takes _O(<u>height</u> log N)_ time.
+    pub fn build_level_index(&mut self) {
+        self.level_starts = Some(self.compute_level_starts());
+    }
+
+    /// Drops the level-boundary index to save memory for very deep trees.
+    ///
+    /// [depth()](#method.depth), [height()](#method.height) and [level_range()](#method.level_range) still work but get slower.
+    pub fn drop_level_index(&mut self) {
+        self.level_starts = None;
+    }
+
+    /// Returns whether this LOUDS has the level-boundary index.
+    pub fn has_level_index(&self) -> bool {
+        self.level_starts.is_some()
+    }
+
+    /// Returns the range of all children of `nodes`, which is contiguous in level order. Applied repeatedly to a node, it gives the range of its descendants in each level.
+    ///
+    /// It takes _O(log N)_ time.
+    pub(crate) fn children_range(&self, nodes: Range<LoudsNodeNum>) -> Range<LoudsNodeNum> {
+        // Children of node `k` are the '1' right after the `k`-th '0', and the '1' before it are root and children of nodes before `k`.
+        let first_child_of =
+            |k: LoudsNodeNum| LoudsNodeNum(self.lbs.rank(self.lbs.select0(k.0).unwrap()) + 1);
+        first_child_of(nodes.start)..first_child_of(nodes.end)
+    }
+
+    /// Returns the content of the level-boundary index computed from LBS.
+    pub(super) fn compute_level_starts(&self) -> Vec<u64> {
+        let mut level_starts = vec![1];
+        level_starts.extend(self.levels().map(|level| level.end.0));
+        level_starts
+    }
+
+    /// Returns an iterator over node ranges of each level from root, using one select per level.
+    fn levels(&self) -> impl Iterator<Item = Range<LoudsNodeNum>> + '_ {
+        // Level [start, end) ends its children at the `end`-th '0', while the level itself begins after the `start`-th '0'.
+        // Between them lie `end - start` '0' and the '1' of all the children, whose node numbers continue from `end`.
+        let mut level = Some((1, 2, self.lbs.select0(1).unwrap()));
+        std::iter::from_fn(move || {
+            let (start, end, start_select0) = level?;
+            let end_select0 = self.lbs.select0(end).unwrap();
+            let n_children = (end_select0 - start_select0) - (end - start);
+            level = (n_children > 0).then(|| (end, end + n_children, end_select0));
+            Some(LoudsNodeNum(start)..LoudsNodeNum(end))
+        })
+    }
+}
+
+#[cfg(test)]
+mod level_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, expected_depths, expected_levels): (&str, Vec<u64>, Vec<(u64, u64)>) = $value;
+                let mut louds = Louds::from(in_s);
+                for _ in 0..2 {
+                    for (i, &expected_depth) in expected_depths.iter().enumerate() {
+                        assert_eq!(louds.depth(LoudsNodeNum(i as u64 + 1)), expected_depth);
+                    }
+                    assert_eq!(louds.height(), expected_levels.len() as u64 - 1);
+                    for (d, &(start, end)) in expected_levels.iter().enumerate() {
+                        assert_eq!(louds.level_range(d as u64), LoudsNodeNum(start)..LoudsNodeNum(end));
+                        assert_eq!(louds.level_width(d as u64), end - start);
+                    }
+                    let n = expected_depths.len() as u64;
+                    assert_eq!(
+                        louds.level_range(expected_levels.len() as u64),
+                        LoudsNodeNum(n + 1)..LoudsNodeNum(n + 1),
+                    );
+                    assert_eq!(louds.level_width(u64::MAX), 0);
+
+                    // Same results without the index.
+                    louds.drop_level_index();
+                    assert!(!louds.has_level_index());
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("10_0", vec!(0), vec!((1, 2))),
+        t2: ("10_10_0", vec!(0, 1), vec!((1, 2), (2, 3))),
+        t3: (
+            "10_1110_10_0_1110_0_0_10_110_0_0_0",
+            vec!(0, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3),
+            vec!((1, 2), (2, 5), (5, 9), (9, 12)),
+        ),
+        t4: ("10_10_10_10_0", vec!(0, 1, 2, 3), vec!((1, 2), (2, 3), (3, 4), (4, 5))),
+    }
+}
+
+#[cfg(test)]
+mod depth_failure_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (in_s, node_num) = $value;
+                let louds = Louds::from(in_s);
+                let _ = louds.depth(LoudsNodeNum(node_num));
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: ("10_0", 0),
+        t1_2: ("10_0", 2),
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 12),
+    }
+}
@@ -0,0 +1,313 @@
+use super::{Louds, LoudsIndex, LoudsNodeNum};
+
+impl Louds {
+    /// Returns the number of nodes.
+    ///
+    /// LBS of _N_ nodes has _N_ '1' and _N + 1_ '0', so it takes _O(1)_ time.
+    pub fn num_nodes(&self) -> u64 {
+        self.view().num_nodes()
+    }
+
+    /// Returns whether `node_num` has no child.
+    ///
+    /// It takes one select and one bit probe.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn is_leaf(&self, node_num: LoudsNodeNum) -> bool {
+        let first_child_index = self.first_child_index(node_num);
+        !self.lbs[first_child_index]
+    }
+
+    /// Same as [is_leaf()](#method.is_leaf) for the node pointed by `index`.
+    ///
+    /// # Panics
+    /// `index` does not point to any node in this LOUDS.
+    pub fn is_leaf_by_index(&self, index: LoudsIndex) -> bool {
+        self.is_leaf(self.index_to_node_num(index))
+    }
+
+    /// Returns the number of children of `node_num`.
+    ///
+    /// It takes _O(log N + <u>degree</u> / 64)_ time.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn degree(&self, node_num: LoudsNodeNum) -> u64 {
+        let first_child_index = self.first_child_index(node_num);
+        self.lbs.run_of_ones(first_child_index)
+    }
+
+    /// Same as [degree()](#method.degree) for the node pointed by `index`.
+    ///
+    /// # Panics
+    /// `index` does not point to any node in this LOUDS.
+    pub fn degree_by_index(&self, index: LoudsIndex) -> u64 {
+        self.degree(self.index_to_node_num(index))
+    }
+
+    /// Returns the first child of `node_num`, or `None` if it is a leaf.
+    ///
+    /// It takes one select.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn first_child(&self, node_num: LoudsNodeNum) -> Option<LoudsNodeNum> {
+        self.nth_child(node_num, 0)
+    }
+
+    /// Returns the last child of `node_num`, or `None` if it is a leaf.
+    ///
+    /// It takes _O(log N + <u>degree</u> / 64)_ time.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn last_child(&self, node_num: LoudsNodeNum) -> Option<LoudsNodeNum> {
+        let first_child_index = self.first_child_index(node_num);
+        match self.lbs.run_of_ones(first_child_index) {
+            0 => None,
+            degree => Some(self.child_node_num(node_num, first_child_index + degree - 1)),
+        }
+    }
+
+    /// Returns the `k`-th (0-origin) child of `node_num`, or `None` if it has `k` or less children.
+    ///
+    /// It takes one select and one rank.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn nth_child(&self, node_num: LoudsNodeNum, k: u64) -> Option<LoudsNodeNum> {
+        let index = self.first_child_index(node_num).saturating_add(k);
+        // No '0' (end of `node_num`'s children) in between.
+        (index < self.lbs.len() && self.lbs[index] && self.lbs.rank0(index) == node_num.0)
+            .then(|| self.child_node_num(node_num, index))
+    }
+
+    /// Returns the next sibling of `node_num`, or `None` if it is the last child (or root).
+    ///
+    /// It takes one select.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn next_sibling(&self, node_num: LoudsNodeNum) -> Option<LoudsNodeNum> {
+        let index = self.node_num_to_index(node_num);
+        self.lbs[index.0 + 1].then(|| LoudsNodeNum(node_num.0 + 1))
+    }
+
+    /// Returns the previous sibling of `node_num`, or `None` if it is the first child (or root).
+    ///
+    /// It takes one select.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn prev_sibling(&self, node_num: LoudsNodeNum) -> Option<LoudsNodeNum> {
+        let index = self.node_num_to_index(node_num);
+        (index.0 > 0 && self.lbs[index.0 - 1]).then(|| LoudsNodeNum(node_num.0 - 1))
+    }
+
+    /// Returns the position (0-origin) of `node_num` among its siblings, or `None` if it is root.
+    ///
+    /// It takes two selects and one rank.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn child_rank(&self, node_num: LoudsNodeNum) -> Option<u64> {
+        let index = self.node_num_to_index(node_num);
+        if index.0 == 0 {
+            return None;
+        }
+        let parent = LoudsNodeNum(self.lbs.rank0(index.0));
+        Some(index.0 - self.first_child_index(parent))
+    }
+
+    /// Returns the node number of the child of `parent` at `index`.
+    ///
+    /// '0' in _[0, `index`]_ are exactly `parent` of them, so the number of '1' is _`index` + 1 - `parent`_.
+    fn child_node_num(&self, parent: LoudsNodeNum, index: u64) -> LoudsNodeNum {
+        LoudsNodeNum(index + 1 - parent.0)
+    }
+
+    /// Returns the position where children of `node_num` start (or where its terminating '0' is if it is a leaf).
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    fn first_child_index(&self, node_num: LoudsNodeNum) -> u64 {
+        self.check_node_num(node_num)
+            .unwrap_or_else(|e| panic!("{}", e));
+        self.lbs.select0(node_num.0).unwrap() + 1
+    }
+}
+
+#[cfg(test)]
+mod num_nodes_success_tests {
+    use crate::Louds;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, expected_num_nodes) = $value;
+                let louds = Louds::from(in_s);
+                assert_eq!(louds.num_nodes(), expected_num_nodes);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("10_0", 1),
+        t2: ("10_10_0", 2),
+        t3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 11),
+    }
+}
+
+#[cfg(test)]
+mod degree_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, node_num, expected_degree) = $value;
+                let louds = Louds::from(in_s);
+                let node_num = LoudsNodeNum(node_num);
+                let index = louds.node_num_to_index(node_num);
+                assert_eq!(louds.degree(node_num), expected_degree);
+                assert_eq!(louds.degree_by_index(index), expected_degree);
+                assert_eq!(louds.is_leaf(node_num), expected_degree == 0);
+                assert_eq!(louds.is_leaf_by_index(index), expected_degree == 0);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: ("10_0", 1, 0),
+
+        t2_1: ("10_10_0", 1, 1),
+        t2_2: ("10_10_0", 2, 0),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 1, 3),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 2, 1),
+        t3_3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 3, 0),
+        t3_4: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 4, 3),
+        t3_5: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 5, 0),
+        t3_6: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 6, 0),
+        t3_7: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 7, 1),
+        t3_8: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 8, 2),
+        t3_9: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 9, 0),
+        t3_10: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 10, 0),
+        t3_11: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 11, 0),
+    }
+}
+
+#[cfg(test)]
+mod degree_failure_tests {
+    use crate::{Louds, LoudsIndex, LoudsNodeNum};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (in_s, node_num) = $value;
+                let louds = Louds::from(in_s);
+                let _ = louds.degree(LoudsNodeNum(node_num));
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: ("10_0", 0),
+        t1_2: ("10_0", 2),
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 12),
+    }
+
+    macro_rules! parameterized_index_not_point_to_node_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (in_s, index) = $value;
+                let louds = Louds::from(in_s);
+                let _ = louds.is_leaf_by_index(LoudsIndex(index));
+            }
+        )*
+        }
+    }
+
+    parameterized_index_not_point_to_node_tests! {
+        t1_3: ("10_0", 1),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 5),
+    }
+}
+
+#[cfg(test)]
+mod child_navigation_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, node_num) = $value;
+                let louds = Louds::from(in_s);
+                let node_num = LoudsNodeNum(node_num);
+                let children: Vec<_> = louds.parent_to_children_nodes(node_num).collect();
+
+                assert_eq!(louds.first_child(node_num), children.first().copied());
+                assert_eq!(louds.last_child(node_num), children.last().copied());
+                for k in 0..=children.len() {
+                    assert_eq!(louds.nth_child(node_num, k as u64), children.get(k).copied());
+                }
+                for (k, &child) in children.iter().enumerate() {
+                    assert_eq!(louds.child_rank(child), Some(k as u64));
+                    assert_eq!(louds.prev_sibling(child), k.checked_sub(1).map(|k| children[k]));
+                    assert_eq!(louds.next_sibling(child), children.get(k + 1).copied());
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: ("10_0", 1),
+
+        t2_1: ("10_10_0", 1),
+        t2_2: ("10_10_0", 2),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 1),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 2),
+        t3_3: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 3),
+        t3_4: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 4),
+        t3_5: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 5),
+        t3_6: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 6),
+        t3_7: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 7),
+        t3_8: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 8),
+        t3_9: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 9),
+        t3_10: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 10),
+        t3_11: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 11),
+    }
+
+    #[test]
+    fn nth_child_out_of_range() {
+        let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+        assert_eq!(louds.nth_child(LoudsNodeNum(11), 1), None);
+        assert_eq!(louds.nth_child(LoudsNodeNum(8), u64::MAX), None);
+    }
+
+    #[test]
+    fn root_has_no_sibling() {
+        let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+        assert_eq!(louds.child_rank(LoudsNodeNum(1)), None);
+        assert_eq!(louds.prev_sibling(LoudsNodeNum(1)), None);
+        assert_eq!(louds.next_sibling(LoudsNodeNum(1)), None);
+    }
+}
@@ -0,0 +1,261 @@
+use super::{Louds, LoudsError, LoudsNodeNum, NodeIdMap};
+
+impl Louds {
+    /// Converts this LOUDS into BP (balanced parentheses): each node in preorder is '(' (`true`), followed by its subtree and ')' (`false`).
+    ///
+    /// Returns BP of _2N_ bits and the bijection between preorder ranks (as ids; root is _0_) and [LoudsNodeNum](struct.LoudsNodeNum.html).
+    /// The node of preorder rank `k` is the _(k + 1)_-th '(' in BP.
+    ///
+    /// It takes _O(N log N)_ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// let louds = Louds::from("10_110_10_0_0");
+    /// let (bp, id_map) = louds.to_balanced_parens();
+    /// let s: String = bp.iter().map(|&open| if open { '(' } else { ')' }).collect();
+    /// assert_eq!(s, "((())())");
+    /// assert_eq!(id_map.ids(), &[0, 1, 3, 2]);
+    /// assert_eq!(id_map.node_num(2), LoudsNodeNum(4));
+    /// ```
+    pub fn to_balanced_parens(&self) -> (Vec<bool>, NodeIdMap) {
+        let mut bp = Vec::with_capacity(2 * self.num_nodes() as usize);
+        let mut n_open = 0;
+        let id_map = self.preorder(|_, depth| {
+            // Close the previous node and its ancestors deeper than this node.
+            bp.resize(bp.len() + (n_open - depth) as usize, false);
+            bp.push(true);
+            n_open = depth + 1;
+        });
+        bp.resize(bp.len() + n_open as usize, false);
+        (bp, id_map)
+    }
+
+    /// Builds [Louds](struct.Louds.html) from BP in the format of [to_balanced_parens()](#method.to_balanced_parens).
+    ///
+    /// Returns the LOUDS and the bijection between preorder ranks (as ids; root is _0_) and [LoudsNodeNum](struct.LoudsNodeNum.html).
+    ///
+    /// It takes _O(N)_ time and _O(N)_ working space.
+    ///
+    /// # Errors
+    /// [LoudsError::UnbalancedParens](enum.LoudsError.html#variant.UnbalancedParens): `bp` is not balanced, or has more than one root.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// let bp: Vec<bool> = "((())())".chars().map(|c| c == '(').collect();
+    /// let (louds, id_map) = Louds::from_balanced_parens(&bp).unwrap();
+    /// assert_eq!(format!("{:#}", louds), "10_110_10_0_0");
+    /// assert_eq!(id_map.node_num(3), LoudsNodeNum(3));
+    /// ```
+    pub fn from_balanced_parens(bp: &[bool]) -> Result<(Louds, NodeIdMap), LoudsError> {
+        check_parens(bp)?;
+
+        // Preorder ranks of the nodes whose ')' is not seen yet.
+        let mut open = Vec::new();
+        let mut parents = Vec::with_capacity(bp.len() / 2);
+        for &paren in bp {
+            if paren {
+                parents.push(open.last().copied());
+                open.push(parents.len() - 1);
+            } else {
+                open.pop();
+            }
+        }
+        Self::from_parents(&parents)
+    }
+
+    /// Converts this LOUDS into DFUDS (depth-first unary degree sequence): '(' (`true`) followed by each node's degree in unary, '(' * _degree_ + ')' (`false`), in preorder.
+    ///
+    /// Returns DFUDS of _2N_ bits and the bijection between preorder ranks (as ids; root is _0_) and [LoudsNodeNum](struct.LoudsNodeNum.html).
+    /// The node of preorder rank `k` ends at the _(k + 1)_-th ')' in DFUDS.
+    ///
+    /// It takes _O(N log N)_ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// let louds = Louds::from("10_110_10_0_0");
+    /// let (dfuds, id_map) = louds.to_dfuds();
+    /// let s: String = dfuds.iter().map(|&open| if open { '(' } else { ')' }).collect();
+    /// assert_eq!(s, "((()()))");
+    /// assert_eq!(id_map.node_num(3), LoudsNodeNum(3));
+    /// ```
+    pub fn to_dfuds(&self) -> (Vec<bool>, NodeIdMap) {
+        let mut dfuds = Vec::with_capacity(2 * self.num_nodes() as usize);
+        dfuds.push(true);
+        let id_map = self.preorder(|node_num, _| {
+            dfuds.resize(dfuds.len() + self.degree(node_num) as usize, true);
+            dfuds.push(false);
+        });
+        (dfuds, id_map)
+    }
+
+    /// Builds [Louds](struct.Louds.html) from DFUDS in the format of [to_dfuds()](#method.to_dfuds).
+    ///
+    /// Returns the LOUDS and the bijection between preorder ranks (as ids; root is _0_) and [LoudsNodeNum](struct.LoudsNodeNum.html).
+    ///
+    /// It takes _O(N)_ time and _O(N)_ working space.
+    ///
+    /// # Errors
+    /// [LoudsError::UnbalancedParens](enum.LoudsError.html#variant.UnbalancedParens): `dfuds` (with the leading '(') is not balanced, or has more than one root.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum};
+    ///
+    /// let dfuds: Vec<bool> = "((()()))".chars().map(|c| c == '(').collect();
+    /// let (louds, id_map) = Louds::from_dfuds(&dfuds).unwrap();
+    /// assert_eq!(format!("{:#}", louds), "10_110_10_0_0");
+    /// assert_eq!(id_map.node_num(2), LoudsNodeNum(4));
+    /// ```
+    pub fn from_dfuds(dfuds: &[bool]) -> Result<(Louds, NodeIdMap), LoudsError> {
+        // DFUDS is balanced as BP is: the leading '(' stands for the root, and each node's '(' for its children, all of which are closed by ')' of the nodes.
+        check_parens(dfuds)?;
+
+        // Nodes (preorder ranks) which still wait for children, with the number of them.
+        let mut waiting: Vec<(usize, usize)> = Vec::new();
+        let mut parents = Vec::with_capacity(dfuds.len() / 2);
+        let mut degree = 0;
+        for &paren in &dfuds[1..] {
+            if paren {
+                degree += 1;
+                continue;
+            }
+            // Nodes come in preorder, so this is the next child of the deepest node still waiting for one.
+            let parent = waiting.last_mut().map(|(parent, n_waiting)| {
+                *n_waiting -= 1;
+                *parent
+            });
+            if let Some((_, 0)) = waiting.last() {
+                waiting.pop();
+            }
+            if degree > 0 {
+                waiting.push((parents.len(), degree));
+                degree = 0;
+            }
+            parents.push(parent);
+        }
+        Self::from_parents(&parents)
+    }
+
+    /// Calls `f` with `(node, depth)` in preorder, and returns the bijection between preorder ranks and node numbers.
+    fn preorder(&self, mut f: impl FnMut(LoudsNodeNum, u64)) -> NodeIdMap {
+        let n = self.num_nodes() as usize;
+        let mut node_nums = Vec::with_capacity(n);
+        let mut ids = vec![0; n];
+        for (node_num, depth) in self.iter_preorder() {
+            ids[node_num.0 as usize - 1] = node_nums.len();
+            node_nums.push(node_num);
+            f(node_num, depth);
+        }
+        NodeIdMap { node_nums, ids }
+    }
+}
+
+/// Checks if `parens` is balanced and the first '(' is closed by the last ')'.
+///
+/// # Errors
+/// [LoudsError::UnbalancedParens](enum.LoudsError.html#variant.UnbalancedParens): Otherwise.
+fn check_parens(parens: &[bool]) -> Result<(), LoudsError> {
+    let mut excess = 0u64;
+    for (i, &paren) in parens.iter().enumerate() {
+        if paren {
+            excess += 1;
+            continue;
+        }
+        if excess == 0 || (excess == 1 && i != parens.len() - 1) {
+            return Err(LoudsError::UnbalancedParens { position: i as u64 });
+        }
+        excess -= 1;
+    }
+    if excess != 0 || parens.is_empty() {
+        return Err(LoudsError::UnbalancedParens {
+            position: parens.len() as u64,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod parens_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    fn to_parens(s: &str) -> Vec<bool> {
+        s.chars().map(|c| c == '(').collect()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (lbs, bp, dfuds, preorder_ranks): (&str, &str, &str, Vec<usize>) = $value;
+                let louds = Louds::from(lbs);
+
+                let (actual_bp, bp_id_map) = louds.to_balanced_parens();
+                assert_eq!(actual_bp, to_parens(bp));
+                let (actual_dfuds, dfuds_id_map) = louds.to_dfuds();
+                assert_eq!(actual_dfuds, to_parens(dfuds));
+                assert_eq!(bp_id_map.ids(), &preorder_ranks[..]);
+                assert_eq!(dfuds_id_map, bp_id_map);
+                for (i, &rank) in preorder_ranks.iter().enumerate() {
+                    assert_eq!(bp_id_map.node_num(rank), LoudsNodeNum(i as u64 + 1));
+                }
+
+                let (from_bp, from_bp_id_map) = Louds::from_balanced_parens(&to_parens(bp)).unwrap();
+                assert_eq!(format!("{:#}", from_bp), lbs);
+                assert_eq!(from_bp_id_map, bp_id_map);
+                let (from_dfuds, from_dfuds_id_map) = Louds::from_dfuds(&to_parens(dfuds)).unwrap();
+                assert_eq!(format!("{:#}", from_dfuds), lbs);
+                assert_eq!(from_dfuds_id_map, bp_id_map);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("10_0", "()", "()", vec!(0)),
+        t2: ("10_10_0", "(())", "(())", vec!(0, 1)),
+        t3: ("10_110_0_0", "(()())", "((()))", vec!(0, 1, 2)),
+        t4: (
+            "10_1110_10_0_1110_0_0_10_110_0_0_0",
+            "((())()(()(())(()())))",
+            "(((()()))((())())(()))",
+            vec!(0, 1, 3, 4, 2, 5, 6, 8, 7, 9, 10),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod parens_failure_tests {
+    use crate::{Louds, LoudsError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (parens, position): (&str, u64) = $value;
+                let parens: Vec<bool> = parens.chars().map(|c| c == '(').collect();
+                let expected_err = LoudsError::UnbalancedParens { position };
+                assert_eq!(Louds::from_balanced_parens(&parens).unwrap_err(), expected_err);
+                assert_eq!(Louds::from_dfuds(&parens).unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("", 0),
+        t2: (")(", 0),
+        t3: ("(", 1),
+        t4: ("(()", 3),
+        // Two roots.
+        t5: ("()()", 1),
+        t6: ("(())()", 3),
+    }
+}
@@ -0,0 +1,288 @@
+use super::{LevelOrderIter, Louds, LoudsNodeNum, PostorderIter, PreorderIter};
+
+impl Louds {
+    /// Returns an iterator over all nodes in level order (i.e. `LoudsNodeNum(1)`, `LoudsNodeNum(2)`, ...), yielding `(node, depth)`.
+    ///
+    /// It takes _O(1)_ time per node plus _O(log N)_ per level, without allocation.
+    pub fn iter_level_order(&self) -> LevelOrderIter<'_> {
+        LevelOrderIter {
+            inner: self,
+            node: 1,
+            depth: 0,
+            level_end: 2,
+        }
+    }
+
+    /// Returns an iterator over all nodes in preorder (parent first, children from left to right), yielding `(node, depth)`.
+    ///
+    /// Traversal uses an explicit stack of _O(<u>height</u>)_ words instead of recursion, so it works on arbitrarily deep trees.
+    /// It takes _O(log N)_ time per node.
+    pub fn iter_preorder(&self) -> PreorderIter<'_> {
+        self.iter_subtree_preorder(LoudsNodeNum(1))
+    }
+
+    /// Returns an iterator over all nodes in postorder (children from left to right, then parent), yielding `(node, depth)`.
+    ///
+    /// Same complexity as [iter_preorder()](#method.iter_preorder).
+    pub fn iter_postorder(&self) -> PostorderIter<'_> {
+        self.iter_subtree_postorder(LoudsNodeNum(1))
+    }
+
+    /// Returns an iterator over `node_num` and its descendants in preorder, yielding `(node, depth)`.
+    ///
+    /// Depths are the ones in the whole tree, not relative to `node_num`.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn iter_subtree_preorder(&self, node_num: LoudsNodeNum) -> PreorderIter<'_> {
+        let index = self.node_num_to_index(node_num);
+        PreorderIter {
+            inner: self,
+            stack: Vec::new(),
+            root: Some(index.0),
+            root_depth: self.depth(node_num),
+        }
+    }
+
+    /// Returns an iterator over `node_num` and its descendants in postorder, yielding `(node, depth)`.
+    ///
+    /// Depths are the ones in the whole tree, not relative to `node_num`.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn iter_subtree_postorder(&self, node_num: LoudsNodeNum) -> PostorderIter<'_> {
+        let index = self.node_num_to_index(node_num);
+        let mut iter = PostorderIter {
+            inner: self,
+            stack: vec![index.0],
+            root_depth: self.depth(node_num),
+        };
+        iter.descend();
+        iter
+    }
+
+    /// Returns the index of the first child of the node at `index`, if any.
+    fn first_child_index_of(&self, index: u64) -> Option<u64> {
+        let first_child = self.lbs.select0(self.lbs.rank(index)).unwrap() + 1;
+        self.lbs[first_child].then_some(first_child)
+    }
+
+    /// Returns the index of the next sibling of the node at `index`, if any.
+    fn next_sibling_index_of(&self, index: u64) -> Option<u64> {
+        // The last '1' is always followed by '0', so `index + 1` is in range.
+        self.lbs[index + 1].then_some(index + 1)
+    }
+}
+
+impl<'a> Iterator for LevelOrderIter<'a> {
+    type Item = (LoudsNodeNum, u64);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.node > self.inner.num_nodes() {
+            return None;
+        }
+        if self.node == self.level_end {
+            // Children of the current level end right before the `level_end`-th '0'.
+            self.depth += 1;
+            self.level_end = self
+                .inner
+                .lbs
+                .rank(self.inner.lbs.select0(self.level_end).unwrap())
+                + 1;
+        }
+        let result = (LoudsNodeNum(self.node), self.depth);
+        self.node += 1;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.inner.num_nodes() + 1 - self.node) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for LevelOrderIter<'a> {}
+
+impl<'a> PreorderIter<'a> {
+    fn emit(&self, index: u64) -> (LoudsNodeNum, u64) {
+        (
+            LoudsNodeNum(self.inner.lbs.rank(index)),
+            self.root_depth + self.stack.len() as u64 - 1,
+        )
+    }
+}
+
+impl<'a> Iterator for PreorderIter<'a> {
+    type Item = (LoudsNodeNum, u64);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            self.stack.push(root);
+            return Some(self.emit(root));
+        }
+
+        let &last = self.stack.last()?;
+        if let Some(first_child) = self.inner.first_child_index_of(last) {
+            self.stack.push(first_child);
+            return Some(self.emit(first_child));
+        }
+        // Go up until a node has the next sibling, but never move the subtree root to its sibling.
+        loop {
+            let last = self.stack.pop().unwrap();
+            if self.stack.is_empty() {
+                return None;
+            }
+            if let Some(next_sibling) = self.inner.next_sibling_index_of(last) {
+                self.stack.push(next_sibling);
+                return Some(self.emit(next_sibling));
+            }
+        }
+    }
+}
+
+impl<'a> PostorderIter<'a> {
+    /// Pushes first children from the top of the stack down to a leaf.
+    fn descend(&mut self) {
+        while let Some(first_child) = self.inner.first_child_index_of(*self.stack.last().unwrap()) {
+            self.stack.push(first_child);
+        }
+    }
+}
+
+impl<'a> Iterator for PostorderIter<'a> {
+    type Item = (LoudsNodeNum, u64);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.stack.pop()?;
+        let result = (
+            LoudsNodeNum(self.inner.lbs.rank(last)),
+            self.root_depth + self.stack.len() as u64,
+        );
+        // Never move the subtree root to its sibling.
+        if !self.stack.is_empty() {
+            if let Some(next_sibling) = self.inner.next_sibling_index_of(last) {
+                self.stack.push(next_sibling);
+                self.descend();
+            }
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod traversal_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, expected_level_order, expected_preorder, expected_postorder): (
+                    &str,
+                    Vec<(u64, u64)>,
+                    Vec<(u64, u64)>,
+                    Vec<(u64, u64)>,
+                ) = $value;
+                let mut louds = Louds::from(in_s);
+                let to_node_nums = |v: Vec<(u64, u64)>| -> Vec<(LoudsNodeNum, u64)> {
+                    v.into_iter().map(|(n, d)| (LoudsNodeNum(n), d)).collect()
+                };
+                for _ in 0..2 {
+                    assert_eq!(louds.iter_level_order().len(), expected_level_order.len());
+                    assert_eq!(louds.iter_level_order().collect::<Vec<_>>(), to_node_nums(expected_level_order.clone()));
+                    assert_eq!(louds.iter_preorder().collect::<Vec<_>>(), to_node_nums(expected_preorder.clone()));
+                    assert_eq!(louds.iter_postorder().collect::<Vec<_>>(), to_node_nums(expected_postorder.clone()));
+
+                    // Same results without the level-boundary index.
+                    louds.drop_level_index();
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("10_0", vec!((1, 0)), vec!((1, 0)), vec!((1, 0))),
+        t2: (
+            "10_10_10_0",
+            vec!((1, 0), (2, 1), (3, 2)),
+            vec!((1, 0), (2, 1), (3, 2)),
+            vec!((3, 2), (2, 1), (1, 0)),
+        ),
+        t3: (
+            "10_1110_10_0_1110_0_0_10_110_0_0_0",
+            vec!((1, 0), (2, 1), (3, 1), (4, 1), (5, 2), (6, 2), (7, 2), (8, 2), (9, 3), (10, 3), (11, 3)),
+            vec!((1, 0), (2, 1), (5, 2), (3, 1), (4, 1), (6, 2), (7, 2), (9, 3), (8, 2), (10, 3), (11, 3)),
+            vec!((5, 2), (2, 1), (3, 1), (6, 2), (9, 3), (7, 2), (10, 3), (11, 3), (8, 2), (4, 1), (1, 0)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod subtree_traversal_success_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_s, node_num, expected_preorder, expected_postorder): (
+                    &str,
+                    u64,
+                    Vec<(u64, u64)>,
+                    Vec<(u64, u64)>,
+                ) = $value;
+                let louds = Louds::from(in_s);
+                let to_node_nums = |v: Vec<(u64, u64)>| -> Vec<(LoudsNodeNum, u64)> {
+                    v.into_iter().map(|(n, d)| (LoudsNodeNum(n), d)).collect()
+                };
+                let node_num = LoudsNodeNum(node_num);
+                assert_eq!(louds.iter_subtree_preorder(node_num).collect::<Vec<_>>(), to_node_nums(expected_preorder));
+                assert_eq!(louds.iter_subtree_postorder(node_num).collect::<Vec<_>>(), to_node_nums(expected_postorder));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: ("10_0", 1, vec!((1, 0)), vec!((1, 0))),
+
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 2, vec!((2, 1), (5, 2)), vec!((5, 2), (2, 1))),
+        t3_2: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 3, vec!((3, 1)), vec!((3, 1))),
+        t3_3: (
+            "10_1110_10_0_1110_0_0_10_110_0_0_0",
+            4,
+            vec!((4, 1), (6, 2), (7, 2), (9, 3), (8, 2), (10, 3), (11, 3)),
+            vec!((6, 2), (9, 3), (7, 2), (10, 3), (11, 3), (8, 2), (4, 1)),
+        ),
+        t3_4: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 7, vec!((7, 2), (9, 3)), vec!((9, 3), (7, 2))),
+        t3_5: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 11, vec!((11, 3)), vec!((11, 3))),
+    }
+}
+
+#[cfg(test)]
+mod subtree_traversal_failure_tests {
+    use crate::{Louds, LoudsNodeNum};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (in_s, node_num) = $value;
+                let louds = Louds::from(in_s);
+                let _ = louds.iter_subtree_preorder(LoudsNodeNum(node_num));
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: ("10_0", 0),
+        t1_2: ("10_0", 2),
+        t3_1: ("10_1110_10_0_1110_0_0_10_110_0_0_0", 12),
+    }
+}
@@ -0,0 +1,256 @@
+use super::louds_io::block_checksums;
+use super::{Louds, LoudsView, VerifyReport};
+use crate::bit_vector::BitVectorView;
+
+impl Louds {
+    /// Checks the LBS's condition, consistency of rank/select directories with LBS, and the level-boundary index, without panicking.
+    ///
+    /// Constructors and [read_from()](#method.read_from) check only what is needed for the input they take, so this is for data from elsewhere, e.g. written from memory already corrupted.
+    /// It takes _O(N)_ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::Louds;
+    ///
+    /// let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+    /// assert!(louds.verify().is_ok());
+    /// ```
+    pub fn verify(&self) -> VerifyReport {
+        let mut report = self.view().verify();
+        if report.is_ok() {
+            if let Some(level_starts) = &self.level_starts {
+                report.inconsistent_level_index = self.compute_level_starts() != *level_starts;
+            }
+        }
+        report
+    }
+}
+
+impl<'a> LoudsView<'a> {
+    /// Same as [Louds::verify()](struct.Louds.html#method.verify), plus the block checksums if they are in the viewed data.
+    ///
+    /// [from_bytes()](#method.from_bytes) does not read all of LBS, so this is the way to find corruption in it, which could otherwise give a wrong tree or panic in navigation.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsView};
+    ///
+    /// let mut bytes = Vec::new();
+    /// Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0").write_to(&mut bytes).unwrap();
+    /// bytes[24] ^= 1 << 2; // "10_0110_..."
+    ///
+    /// let mut buf = vec![0u8; bytes.len() + 7];
+    /// let offset = buf.as_ptr().align_offset(8);
+    /// buf[offset..offset + bytes.len()].copy_from_slice(&bytes);
+    /// let view = LoudsView::from_bytes(&buf[offset..offset + bytes.len()]).unwrap();
+    ///
+    /// let report = view.verify();
+    /// assert!(!report.is_ok());
+    /// assert_eq!(report.corrupted_blocks, vec![0]);
+    /// assert_eq!(report.inconsistent_rank_blocks, vec![0]);
+    /// ```
+    pub fn verify(&self) -> VerifyReport {
+        // A view of deserialized Louds may have any sizes, which the other checks assume.
+        let lbs = self.lbs;
+        if BitVectorView::from_parts(lbs.words(), lbs.len(), lbs.directories()).is_none() {
+            return VerifyReport {
+                invalid_layout: true,
+                ..VerifyReport::default()
+            };
+        }
+        let corrupted_blocks = match self.block_checksums {
+            Some(expected) => block_checksums(self.lbs.words())
+                .iter()
+                .zip(expected)
+                .enumerate()
+                .filter(|(_, (actual, expected))| actual != expected)
+                .map(|(b, _)| b as u64)
+                .collect(),
+            None => Vec::new(),
+        };
+        let [inconsistent_select1_hints, inconsistent_select0_hints] =
+            self.lbs.inconsistent_select_hints();
+        VerifyReport {
+            corrupted_blocks,
+            lbs_error: Louds::check_lbs(self.lbs).err(),
+            inconsistent_rank_blocks: self.lbs.inconsistent_rank_blocks(),
+            inconsistent_select1_hints,
+            inconsistent_select0_hints,
+            inconsistent_level_index: false,
+            invalid_layout: false,
+        }
+    }
+}
+
+impl VerifyReport {
+    /// Returns whether nothing is wrong.
+    pub fn is_ok(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod verify_success_tests {
+    use crate::{Louds, LoudsView};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let s: &str = $value;
+                let louds = Louds::from(s);
+                assert!(louds.verify().is_ok());
+
+                let mut bytes = Vec::new();
+                louds.write_to(&mut bytes).unwrap();
+                let mut buf = vec![0u8; bytes.len() + 7];
+                let offset = buf.as_ptr().align_offset(8);
+                buf[offset..offset + bytes.len()].copy_from_slice(&bytes);
+                let view = LoudsView::from_bytes(&buf[offset..offset + bytes.len()]).unwrap();
+                assert!(view.verify().is_ok());
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: "10_0",
+        t2: "10_10_0",
+        t3: "10_1110_10_0_1110_0_0_10_110_0_0_0",
+        t4: &format!("10_{}0{}", "1".repeat(70_000), "_0".repeat(70_000)),
+        t5: &format!("10{}_0", "_10".repeat(70_000)),
+    }
+}
+
+#[cfg(test)]
+mod verify_failure_tests {
+    use crate::bit_vector::BitVector;
+    use crate::{Louds, LoudsError, LoudsView, VerifyReport};
+
+    const LBS: &str = "10_1110_10_0_1110_0_0_10_110_0_0_0";
+
+    /// Returns `Louds::from(s)` with its words and directories modified by `f`.
+    fn corrupted(s: &str, f: impl FnOnce(&mut Vec<u64>, &mut [Vec<u64>; 3])) -> Louds {
+        let louds = Louds::from(s);
+        let mut words = louds.lbs.words().to_vec();
+        let mut directories = louds.lbs.directories().map(|directory| directory.to_vec());
+        f(&mut words, &mut directories);
+        Louds {
+            lbs: BitVector::from_parts(words, louds.lbs.len(), directories).unwrap(),
+            level_starts: louds.level_starts,
+        }
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (louds, expected): (Louds, VerifyReport) = $value;
+                assert_eq!(louds.verify(), expected);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        // "10_0110_..."
+        t1: (
+            corrupted(LBS, |words, _| words[0] ^= 1 << 2),
+            VerifyReport {
+                lbs_error: Some(Louds::try_from_str("10_0110_10_0_1110_0_0_10_110_0_0_0").unwrap_err()),
+                inconsistent_rank_blocks: vec!(0),
+                ..VerifyReport::default()
+            },
+        ),
+        // One more '1' in the rank directory.
+        t2: (
+            corrupted(LBS, |_, [block_ranks, _, _]| block_ranks[1] += 1),
+            VerifyReport { inconsistent_rank_blocks: vec!(0), ..VerifyReport::default() },
+        ),
+        // The 4,097-th '1' is at 4,097 in block 8.
+        t3: (
+            corrupted(&format!("10_{}0{}", "1".repeat(5000), "_0".repeat(5000)), |_, [_, select1_hints, _]| select1_hints[1] = 7),
+            VerifyReport { inconsistent_select1_hints: vec!(1), ..VerifyReport::default() },
+        ),
+        t4: (
+            corrupted(&format!("10_{}0{}", "1".repeat(5000), "_0".repeat(5000)), |_, [_, _, select0_hints]| select0_hints[0] = 1),
+            VerifyReport { inconsistent_select0_hints: vec!(0), ..VerifyReport::default() },
+        ),
+        // "10_0_10", where node 2 would be its own child.
+        t5: (
+            corrupted("10_10_0", |words, _| words[0] ^= 0b1100),
+            VerifyReport {
+                lbs_error: Some(LoudsError::UnbalancedPrefix { position: 2 }),
+                ..VerifyReport::default()
+            },
+        ),
+    }
+
+    #[test]
+    fn invalid_layout() {
+        let louds = Louds::from(LBS);
+        let [block_ranks, select1_hints, select0_hints] = louds.lbs.directories();
+        let words = louds.lbs.words();
+        for (words, len, directories) in [
+            // Block ranks one element short.
+            (
+                words,
+                louds.lbs.len(),
+                [&block_ranks[..1], select1_hints, select0_hints],
+            ),
+            // More bits than words.
+            (words, 65, [block_ranks, select1_hints, select0_hints]),
+        ] {
+            let louds = Louds {
+                lbs: BitVector::from_parts_unchecked(
+                    words.to_vec(),
+                    len,
+                    directories.map(|directory| directory.to_vec()),
+                ),
+                level_starts: louds.level_starts.clone(),
+            };
+            let expected = VerifyReport {
+                invalid_layout: true,
+                ..VerifyReport::default()
+            };
+            assert_eq!(louds.verify(), expected);
+            assert_eq!(louds.view().verify(), expected);
+        }
+    }
+
+    #[test]
+    fn level_index() {
+        let mut louds = Louds::from(LBS);
+        louds.level_starts.as_mut().unwrap()[1] += 1;
+        assert_eq!(
+            louds.verify(),
+            VerifyReport {
+                inconsistent_level_index: true,
+                ..VerifyReport::default()
+            }
+        );
+    }
+
+    #[test]
+    fn corrupted_blocks() {
+        let louds = Louds::from(format!("10{}_0", "_10".repeat(70_000)).as_str());
+        let mut bytes = Vec::new();
+        louds.write_to(&mut bytes).unwrap();
+        // "01" at bit 131,073 (in block 2) turns into "10", which keeps LBS valid and directories consistent.
+        bytes[24 + 8 * 2048] ^= 0b110;
+
+        let mut buf = vec![0u8; bytes.len() + 7];
+        let offset = buf.as_ptr().align_offset(8);
+        buf[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        let view = LoudsView::from_bytes(&buf[offset..offset + bytes.len()]).unwrap();
+        assert_eq!(
+            view.verify(),
+            VerifyReport {
+                corrupted_blocks: vec!(2),
+                ..VerifyReport::default()
+            }
+        );
+    }
+}
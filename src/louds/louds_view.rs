@@ -0,0 +1,468 @@
+use super::louds_io::{
+    CHECKSUM_BLOCK_WORDS, FLAG_BLOCK_CHECKSUMS, FLAG_DIRECTORIES, FLAG_LEVEL_INDEX, MAGIC, VERSION,
+};
+use super::{
+    AncestorNodeIter, ChildIndexIter, ChildNodeIter, Louds, LoudsError, LoudsIndex, LoudsNodeNum,
+    LoudsView,
+};
+use crate::bit_vector::BitVectorView;
+
+impl Louds {
+    /// Returns the borrowed view of this LOUDS, which navigation goes through.
+    pub fn view(&self) -> LoudsView<'_> {
+        LoudsView {
+            lbs: self.lbs.view(),
+            block_checksums: None,
+        }
+    }
+}
+
+impl<'a> LoudsView<'a> {
+    /// Interprets `bytes` written by [Louds::write_to()](struct.Louds.html#method.write_to) as LOUDS, without copying LBS or rebuilding its directories.
+    ///
+    /// `bytes` is typically a memory-mapped file. The layout is documented in [Louds::write_to()](struct.Louds.html#method.write_to):
+    /// every section is a multiple of 8 bytes, so the words of LBS and directories are used in place.
+    /// The level-boundary index, if any, is skipped since the view does not use it.
+    ///
+    /// It takes _O(N / 512)_ time to check the sizes and ranges of directories.
+    /// Unlike [Louds::read_from()](struct.Louds.html#method.read_from), the checksum is not verified because it requires reading all of `bytes`;
+    /// only the first two bits and the number of '0' and '1' of LBS are checked. Use [verify()](#method.verify) to check the rest.
+    ///
+    /// Available only on little-endian targets, where `u64` words in `bytes` are in the native byte order.
+    ///
+    /// # Errors
+    /// - [LoudsError::Misaligned](enum.LoudsError.html#variant.Misaligned): `bytes` does not start at an address aligned to 8 bytes.
+    /// - [LoudsError::InvalidMagic](enum.LoudsError.html#variant.InvalidMagic): `bytes` does not start from the magic number.
+    /// - [LoudsError::UnsupportedVersion](enum.LoudsError.html#variant.UnsupportedVersion): `bytes` is written by a newer version of louds-rs.
+    /// - [LoudsError::InvalidLayout](enum.LoudsError.html#variant.InvalidLayout): Unknown flags are set, directories are not written (as by [Louds::write_compact_to()](struct.Louds.html#method.write_compact_to)), the sizes of sections do not match, or `bytes` is truncated.
+    /// - [LoudsError::InvalidPrefix](enum.LoudsError.html#variant.InvalidPrefix) and [LoudsError::Unbalanced](enum.LoudsError.html#variant.Unbalanced): LBS is invalid.
+    ///
+    /// # Panics
+    /// This function does not panic, but navigation on the returned view may panic (or give a wrong tree) if LBS or directories are corrupted in a way the checks above do not find.
+    /// Call [verify()](#method.verify) before navigating untrusted data.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Louds, LoudsNodeNum, LoudsView};
+    ///
+    /// let louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+    /// let mut bytes = Vec::new();
+    /// louds.write_to(&mut bytes).unwrap();
+    ///
+    /// // Place the data at an 8-byte aligned address, as mmap does.
+    /// let mut buf = vec![0u8; bytes.len() + 7];
+    /// let offset = buf.as_ptr().align_offset(8);
+    /// buf[offset..offset + bytes.len()].copy_from_slice(&bytes);
+    ///
+    /// let view = LoudsView::from_bytes(&buf[offset..offset + bytes.len()]).unwrap();
+    /// assert_eq!(view.parent_to_children(LoudsNodeNum(4)), louds.parent_to_children(LoudsNodeNum(4)));
+    /// ```
+    #[cfg(target_endian = "little")]
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, LoudsError> {
+        if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+            return Err(LoudsError::InvalidMagic);
+        }
+        if bytes.as_ptr().align_offset(8) != 0 {
+            return Err(LoudsError::Misaligned);
+        }
+        // SAFETY: The pointer is aligned to 8 bytes and the slice covers `bytes.len() / 8 * 8` bytes of `bytes`, which live for `'a` and are not mutated meanwhile.
+        // Any bit pattern is a valid `u64`, and words are little-endian as the target.
+        let mut words: &'a [u64] =
+            unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const u64, bytes.len() / 8) };
+
+        let header = take_words(&mut words, 3)?;
+        let version = header[1] as u32;
+        if version != VERSION {
+            return Err(LoudsError::UnsupportedVersion { version });
+        }
+        let flags = (header[1] >> 32) as u32;
+        if flags & !(FLAG_DIRECTORIES | FLAG_LEVEL_INDEX | FLAG_BLOCK_CHECKSUMS) != 0
+            || flags & FLAG_DIRECTORIES == 0
+        {
+            return Err(LoudsError::InvalidLayout);
+        }
+
+        let len = header[2];
+        let lbs_words = take_words(&mut words, len.div_ceil(64))?;
+        let directories = [
+            take_section(&mut words)?,
+            take_section(&mut words)?,
+            take_section(&mut words)?,
+        ];
+        if flags & FLAG_LEVEL_INDEX != 0 {
+            take_section(&mut words)?;
+        }
+        let block_checksums = if flags & FLAG_BLOCK_CHECKSUMS != 0 {
+            let block_checksums = take_section(&mut words)?;
+            if block_checksums.len() != lbs_words.len().div_ceil(CHECKSUM_BLOCK_WORDS) {
+                return Err(LoudsError::InvalidLayout);
+            }
+            Some(block_checksums)
+        } else {
+            None
+        };
+        // Only the checksum (4 bytes) follows.
+        if !words.is_empty() || bytes.len() % 8 != 4 {
+            return Err(LoudsError::InvalidLayout);
+        }
+
+        let lbs = BitVectorView::from_parts(lbs_words, len, directories)
+            .ok_or(LoudsError::InvalidLayout)?;
+        Self::check_counts(lbs)?;
+        Ok(Self {
+            lbs,
+            block_checksums,
+        })
+    }
+
+    /// Checks the first two bits and the number of '0' and '1' of `lbs`, which are necessary for the LBS's condition.
+    ///
+    /// It takes _O(1)_ time.
+    pub(super) fn check_counts(lbs: BitVectorView<'_>) -> Result<(), LoudsError> {
+        if lbs.len() < 2 || !lbs[0] || lbs[1] {
+            return Err(LoudsError::InvalidPrefix);
+        }
+        let num_ones = lbs.count_ones();
+        if lbs.len() - num_ones != num_ones + 1 {
+            return Err(LoudsError::Unbalanced {
+                num_zeros: lbs.len() - num_ones,
+                num_ones,
+            });
+        }
+        Ok(())
+    }
+
+    /// Same as [Louds::num_nodes()](struct.Louds.html#method.num_nodes).
+    pub fn num_nodes(&self) -> u64 {
+        (self.lbs.len() - 1) / 2
+    }
+
+    /// Same as [Louds::node_num_to_index()](struct.Louds.html#method.node_num_to_index).
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn node_num_to_index(&self, node_num: LoudsNodeNum) -> LoudsIndex {
+        self.try_node_num_to_index(node_num)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible version of [node_num_to_index()](#method.node_num_to_index).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_node_num_to_index(&self, node_num: LoudsNodeNum) -> Result<LoudsIndex, LoudsError> {
+        self.check_node_num(node_num)?;
+
+        let index = self
+            .lbs
+            .select(node_num.0)
+            .ok_or(LoudsError::NodeNotFound(node_num))?;
+        Ok(LoudsIndex(index))
+    }
+
+    /// Same as [Louds::index_to_node_num()](struct.Louds.html#method.index_to_node_num).
+    ///
+    /// # Panics
+    /// `index` does not point to any node in this LOUDS.
+    pub fn index_to_node_num(&self, index: LoudsIndex) -> LoudsNodeNum {
+        self.try_index_to_node_num(index)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible version of [index_to_node_num()](#method.index_to_node_num).
+    ///
+    /// # Errors
+    /// [LoudsError::IndexNotNode](enum.LoudsError.html#variant.IndexNotNode): `index` does not point to any node in this LOUDS.
+    pub fn try_index_to_node_num(&self, index: LoudsIndex) -> Result<LoudsNodeNum, LoudsError> {
+        self.check_index(index)?;
+
+        let node_num = self.lbs.rank(index.0);
+        Ok(LoudsNodeNum(node_num))
+    }
+
+    /// Same as [Louds::child_to_parent()](struct.Louds.html#method.child_to_parent).
+    ///
+    /// # Panics
+    /// - `index` does not point to any node in this LOUDS.
+    /// - `index == 0`: (node#1 is root and doesn't have parent)
+    pub fn child_to_parent(&self, index: LoudsIndex) -> LoudsNodeNum {
+        self.try_child_to_parent(index)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible version of [child_to_parent()](#method.child_to_parent).
+    ///
+    /// # Errors
+    /// - [LoudsError::IndexNotNode](enum.LoudsError.html#variant.IndexNotNode): `index` does not point to any node in this LOUDS.
+    /// - [LoudsError::RootHasNoParent](enum.LoudsError.html#variant.RootHasNoParent): `index == 0`.
+    pub fn try_child_to_parent(&self, index: LoudsIndex) -> Result<LoudsNodeNum, LoudsError> {
+        self.check_index(index)?;
+        if index.0 == 0 {
+            return Err(LoudsError::RootHasNoParent);
+        }
+
+        let parent_node_num = self.lbs.rank0(index.0);
+        Ok(LoudsNodeNum(parent_node_num))
+    }
+
+    /// Same as [Louds::child_to_ancestors()](struct.Louds.html#method.child_to_ancestors).
+    pub fn child_to_ancestors(&self, child: LoudsNodeNum) -> AncestorNodeIter<'a> {
+        AncestorNodeIter {
+            inner: *self,
+            node: child,
+        }
+    }
+
+    /// Fallible version of [child_to_ancestors()](#method.child_to_ancestors).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `child` does not exist in this LOUDS.
+    pub fn try_child_to_ancestors(
+        &self,
+        child: LoudsNodeNum,
+    ) -> Result<AncestorNodeIter<'a>, LoudsError> {
+        self.check_node_num(child)?;
+        Ok(self.child_to_ancestors(child))
+    }
+
+    /// Same as [Louds::parent_to_children()](struct.Louds.html#method.parent_to_children).
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn parent_to_children(&self, node_num: LoudsNodeNum) -> Vec<LoudsIndex> {
+        self.parent_to_children_indices(node_num).collect()
+    }
+
+    /// Fallible version of [parent_to_children()](#method.parent_to_children).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_parent_to_children(
+        &self,
+        node_num: LoudsNodeNum,
+    ) -> Result<Vec<LoudsIndex>, LoudsError> {
+        Ok(self.try_parent_to_children_indices(node_num)?.collect())
+    }
+
+    /// Same as [Louds::parent_to_children_indices()](struct.Louds.html#method.parent_to_children_indices).
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn parent_to_children_indices(&self, node_num: LoudsNodeNum) -> ChildIndexIter<'a> {
+        self.try_parent_to_children_indices(node_num)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible version of [parent_to_children_indices()](#method.parent_to_children_indices).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_parent_to_children_indices(
+        &self,
+        node_num: LoudsNodeNum,
+    ) -> Result<ChildIndexIter<'a>, LoudsError> {
+        self.check_node_num(node_num)?;
+
+        Ok(ChildIndexIter {
+            inner: *self,
+            node: node_num,
+            start: None,
+            end: None,
+        })
+    }
+
+    /// Same as [Louds::parent_to_children_nodes()](struct.Louds.html#method.parent_to_children_nodes).
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this LOUDS.
+    pub fn parent_to_children_nodes(&self, node_num: LoudsNodeNum) -> ChildNodeIter<'a> {
+        ChildNodeIter(self.parent_to_children_indices(node_num))
+    }
+
+    /// Fallible version of [parent_to_children_nodes()](#method.parent_to_children_nodes).
+    ///
+    /// # Errors
+    /// [LoudsError::NodeNotFound](enum.LoudsError.html#variant.NodeNotFound): `node_num` does not exist in this LOUDS.
+    pub fn try_parent_to_children_nodes(
+        &self,
+        node_num: LoudsNodeNum,
+    ) -> Result<ChildNodeIter<'a>, LoudsError> {
+        Ok(ChildNodeIter(
+            self.try_parent_to_children_indices(node_num)?,
+        ))
+    }
+
+    /// Checks if `node_num` exists in this LOUDS.
+    pub(super) fn check_node_num(&self, node_num: LoudsNodeNum) -> Result<(), LoudsError> {
+        if node_num.0 == 0 || node_num.0 > self.num_nodes() {
+            return Err(LoudsError::NodeNotFound(node_num));
+        }
+        Ok(())
+    }
+
+    /// Checks if `index` points to any node in this LOUDS.
+    fn check_index(&self, index: LoudsIndex) -> Result<(), LoudsError> {
+        if index.0 >= self.lbs.len() || !self.lbs[index.0] {
+            return Err(LoudsError::IndexNotNode(index));
+        }
+        Ok(())
+    }
+}
+
+/// Splits the first `n` words off `words`.
+///
+/// # Errors
+/// [LoudsError::InvalidLayout](enum.LoudsError.html#variant.InvalidLayout): `words` has less than `n` words.
+#[cfg(target_endian = "little")]
+fn take_words<'a>(words: &mut &'a [u64], n: u64) -> Result<&'a [u64], LoudsError> {
+    if n > words.len() as u64 {
+        return Err(LoudsError::InvalidLayout);
+    }
+    let (head, tail) = words.split_at(n as usize);
+    *words = tail;
+    Ok(head)
+}
+
+/// Splits a section (`u64` length _n_ followed by _n_ `u64`) off `words`, and returns its content.
+#[cfg(target_endian = "little")]
+fn take_section<'a>(words: &mut &'a [u64]) -> Result<&'a [u64], LoudsError> {
+    let n = take_words(words, 1)?[0];
+    take_words(words, n)
+}
+
+#[cfg(test)]
+mod from_bytes_success_tests {
+    use crate::{Louds, LoudsIndex, LoudsNodeNum, LoudsView};
+
+    /// Copies `bytes` to an 8-byte aligned address in `buf`, and returns the copy.
+    fn aligned<'a>(buf: &'a mut Vec<u8>, bytes: &[u8]) -> &'a [u8] {
+        *buf = vec![0; bytes.len() + 7];
+        let offset = buf.as_ptr().align_offset(8);
+        buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+        &buf[offset..offset + bytes.len()]
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let s: &str = $value;
+                let louds = Louds::from(s);
+                let mut bytes = Vec::new();
+                louds.write_to(&mut bytes).unwrap();
+                let mut buf = Vec::new();
+                let view = LoudsView::from_bytes(aligned(&mut buf, &bytes)).unwrap();
+
+                assert_eq!(view.num_nodes(), louds.num_nodes());
+                for node_num in 1..=louds.num_nodes() {
+                    let node_num = LoudsNodeNum(node_num);
+                    let index = louds.node_num_to_index(node_num);
+                    assert_eq!(view.node_num_to_index(node_num), index);
+                    assert_eq!(view.index_to_node_num(index), node_num);
+                    assert_eq!(view.parent_to_children(node_num), louds.parent_to_children(node_num));
+                    assert_eq!(
+                        view.parent_to_children_nodes(node_num).rev().collect::<Vec<_>>(),
+                        louds.parent_to_children_nodes(node_num).rev().collect::<Vec<_>>(),
+                    );
+                    assert_eq!(
+                        view.child_to_ancestors(node_num).collect::<Vec<_>>(),
+                        louds.child_to_ancestors(node_num).collect::<Vec<_>>(),
+                    );
+                    if node_num.0 > 1 {
+                        assert_eq!(view.child_to_parent(index), louds.child_to_parent(index));
+                    }
+                }
+                assert_eq!(view.try_child_to_parent(LoudsIndex(0)), louds.try_child_to_parent(LoudsIndex(0)));
+                let past_end = LoudsNodeNum(louds.num_nodes() + 1);
+                assert_eq!(view.try_node_num_to_index(past_end), louds.try_node_num_to_index(past_end));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: "10_0",
+        t2: "10_10_0",
+        t3: "10_1110_10_0_1110_0_0_10_110_0_0_0",
+        t4: &format!("10_{}0{}", "1".repeat(5000), "_0".repeat(5000)),
+        t5: &format!("10{}_0", "_10".repeat(5000)),
+    }
+
+    #[test]
+    fn without_level_index() {
+        let mut louds = Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0");
+        louds.drop_level_index();
+        let mut bytes = Vec::new();
+        louds.write_to(&mut bytes).unwrap();
+        let mut buf = Vec::new();
+        let view = LoudsView::from_bytes(aligned(&mut buf, &bytes)).unwrap();
+        assert_eq!(
+            view.parent_to_children(LoudsNodeNum(8)),
+            vec!(LoudsIndex(17), LoudsIndex(18))
+        );
+    }
+}
+
+#[cfg(test)]
+mod from_bytes_failure_tests {
+    use crate::{Louds, LoudsError, LoudsView};
+
+    fn bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0")
+            .write_to(&mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    /// Returns the error of viewing `bytes` placed at an address `misalignment` bytes after an 8-byte boundary.
+    fn view_err(bytes: &[u8], misalignment: usize) -> LoudsError {
+        let mut buf = vec![0u8; bytes.len() + 15];
+        let offset = buf.as_ptr().align_offset(8) + misalignment;
+        buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+        LoudsView::from_bytes(&buf[offset..offset + bytes.len()]).unwrap_err()
+    }
+
+    fn modified(f: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+        let mut bytes = bytes();
+        f(&mut bytes);
+        bytes
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (bytes, misalignment, expected_err): (Vec<u8>, usize, LoudsError) = $value;
+                assert_eq!(view_err(&bytes, misalignment), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        misaligned: (bytes(), 4, LoudsError::Misaligned),
+        empty: (vec!(), 0, LoudsError::InvalidMagic),
+        magic: (modified(|b| b[0] = b'l'), 0, LoudsError::InvalidMagic),
+        version: (modified(|b| b[8] = 2), 0, LoudsError::UnsupportedVersion { version: 2 }),
+        unknown_flag: (modified(|b| b[12] |= 8), 0, LoudsError::InvalidLayout),
+        truncated: (modified(|b| b.truncate(b.len() - 5)), 0, LoudsError::InvalidLayout),
+        trailing: (modified(|b| b.extend_from_slice(&[0; 8])), 0, LoudsError::InvalidLayout),
+        // No block checksum while LBS has a block.
+        block_checksums: (modified(|b| { let n = b.len(); b[n - 4 - 16] = 0 }), 0, LoudsError::InvalidLayout),
+        huge_length: (modified(|b| b[16..24].copy_from_slice(&u64::MAX.to_le_bytes())), 0, LoudsError::InvalidLayout),
+        // LBS "01..." with its directories as is.
+        prefix: (modified(|b| b[24] ^= 3), 0, LoudsError::InvalidPrefix),
+    }
+
+    #[test]
+    fn compact() {
+        let mut bytes = Vec::new();
+        Louds::from("10_1110_10_0_1110_0_0_10_110_0_0_0")
+            .write_compact_to(&mut bytes)
+            .unwrap();
+        assert_eq!(view_err(&bytes, 0), LoudsError::InvalidLayout);
+    }
+}
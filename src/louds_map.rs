@@ -0,0 +1,43 @@
+mod louds_map_impl;
+
+use crate::{LoudsTrie, PredictiveSearchIter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbg, MemSize};
+
+/// Map from keys (sequences of `L`) to values of `V`, over [LoudsTrie](struct.LoudsTrie.html).
+///
+/// Values are held in a dense `Vec<V>` indexed by the rank of the key's terminal node, so space for values is proportional to the number of keys rather than nodes.
+///
+/// # Examples
+/// ```
+/// use louds_rs::LoudsMap;
+///
+/// let map = LoudsMap::from_sorted_iter([("a", 1), ("an", 2), ("and", 3), ("bob", 4)].map(|(k, v)| (k.as_bytes(), v))).unwrap();
+/// assert_eq!(map.get(b"an"), Some(&2));
+/// assert_eq!(map.get(b"bo"), None);
+///
+/// let values: Vec<&i32> = map.get_by_prefix(b"an").map(|(_, value)| value).collect();
+/// assert_eq!(values, vec![&2, &3]);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub struct LoudsMap<L, V> {
+    trie: LoudsTrie<L>,
+
+    /// `values[r]` is the value of the key whose terminal node is the `r`-th (0-origin) terminal node in level order.
+    values: Vec<V>,
+}
+
+/// Iterator over entries of [LoudsMap](struct.LoudsMap.html) in lexicographic order of keys, yielding `(key, value)`.
+///
+/// Returned by [LoudsMap::iter()](struct.LoudsMap.html#method.iter) and [LoudsMap::get_by_prefix()](struct.LoudsMap.html#method.get_by_prefix).
+/// [next_entry()](#method.next_entry) lends the key from a buffer reused across entries, while [Iterator::next()] clones it into a `Vec`.
+pub struct LoudsMapIter<'a, L, V> {
+    map: &'a LoudsMap<L, V>,
+    keys: PredictiveSearchIter<'a, L>,
+}
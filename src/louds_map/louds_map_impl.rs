@@ -0,0 +1,210 @@
+use super::{LoudsMap, LoudsMapIter};
+use crate::{LoudsError, LoudsTrie};
+
+impl<L: Ord + Clone, V> LoudsMap<L, V> {
+    /// Builds a map from `(key, value)` pairs sorted by key in lexicographic order.
+    ///
+    /// It takes _O(<u>total length of keys</u>)_ time.
+    ///
+    /// # Errors
+    /// - [LoudsError::UnsortedKeys](enum.LoudsError.html#variant.UnsortedKeys): A key is smaller than the previous one.
+    /// - [LoudsError::DuplicateKey](enum.LoudsError.html#variant.DuplicateKey): A key is equal to the previous one.
+    pub fn from_sorted_iter<I, K>(entries: I) -> Result<Self, LoudsError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[L]>,
+    {
+        let (keys, values): (Vec<K>, Vec<V>) = entries.into_iter().unzip();
+        for position in 1..keys.len() {
+            match keys[position].as_ref().cmp(keys[position - 1].as_ref()) {
+                std::cmp::Ordering::Less => return Err(LoudsError::UnsortedKeys { position }),
+                std::cmp::Ordering::Equal => return Err(LoudsError::DuplicateKey { position }),
+                std::cmp::Ordering::Greater => {}
+            }
+        }
+
+        // Reorder values from key order to level order of terminal nodes.
+        let mut values: Vec<Option<V>> = values.into_iter().map(Some).collect();
+        let mut level_ordered_values = Vec::with_capacity(values.len());
+        let trie = LoudsTrie::build(&keys, None, |i| {
+            level_ordered_values.push(values[i].take().unwrap())
+        });
+        Ok(Self {
+            trie,
+            values: level_ordered_values,
+        })
+    }
+
+    /// Returns the value of `key`.
+    ///
+    /// Same time complexity as [LoudsTrie::get_node()](struct.LoudsTrie.html#method.get_node) plus _O(1)_ rank.
+    pub fn get(&self, key: &[L]) -> Option<&V> {
+        self.trie
+            .get_node(key)
+            .map(|node_num| &self.values[self.trie.terminal_rank(node_num) as usize])
+    }
+
+    /// Returns whether `key` is in this map.
+    pub fn contains_key(&self, key: &[L]) -> bool {
+        self.trie.contains(key)
+    }
+
+    /// Returns an iterator over entries whose keys start with `prefix`, in lexicographic order of keys.
+    ///
+    /// Same time complexity as [LoudsTrie::predictive_search()](struct.LoudsTrie.html#method.predictive_search).
+    pub fn get_by_prefix(&self, prefix: &[L]) -> LoudsMapIter<'_, L, V> {
+        LoudsMapIter {
+            map: self,
+            keys: self.trie.predictive_search(prefix),
+        }
+    }
+
+    /// Returns an iterator over all entries in lexicographic order of keys.
+    pub fn iter(&self) -> LoudsMapIter<'_, L, V> {
+        self.get_by_prefix(&[])
+    }
+
+    /// Returns an iterator over all keys in lexicographic order.
+    pub fn keys(&self) -> impl Iterator<Item = Vec<L>> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over all values in lexicographic order of their keys.
+    ///
+    /// Keys are not materialized, so it does not allocate per value.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        let mut iter = self.iter();
+        std::iter::from_fn(move || iter.next_entry().map(|(_, value)| value))
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether this map has no key.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the underlying [LoudsTrie](struct.LoudsTrie.html).
+    pub fn trie(&self) -> &LoudsTrie<L> {
+        &self.trie
+    }
+}
+
+impl<'a, L: Ord + Clone, V> LoudsMapIter<'a, L, V> {
+    /// Advances the iterator and returns the next key and its value.
+    ///
+    /// The key is borrowed from a buffer reused across entries, so no allocation happens per entry.
+    pub fn next_entry(&mut self) -> Option<(&[L], &'a V)> {
+        let map = self.map;
+        self.keys
+            .next_key()
+            .map(|(key, node_num)| (key, &map.values[map.trie.terminal_rank(node_num) as usize]))
+    }
+}
+
+impl<'a, L: Ord + Clone, V> Iterator for LoudsMapIter<'a, L, V> {
+    type Item = (Vec<L>, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().map(|(key, value)| (key.to_vec(), value))
+    }
+}
+
+#[cfg(test)]
+mod from_sorted_iter_failure_tests {
+    use crate::{LoudsError, LoudsMap};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_err): (&[&str], LoudsError) = $value;
+                let result = LoudsMap::from_sorted_iter(keys.iter().map(|key| (key.as_bytes(), ())));
+                assert_eq!(result.unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&["b", "a"], LoudsError::UnsortedKeys { position: 1 }),
+        t2: (&["a", "ab", "aa"], LoudsError::UnsortedKeys { position: 2 }),
+        t3: (&["a", "a"], LoudsError::DuplicateKey { position: 1 }),
+        t4: (&["", "a", "b", "b"], LoudsError::DuplicateKey { position: 3 }),
+    }
+}
+
+#[cfg(test)]
+mod get_tests {
+    use crate::LoudsMap;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, query, expected): (&[&str], &str, Option<usize>) = $value;
+                // Value of each key is its position in `keys`.
+                let map = LoudsMap::from_sorted_iter(keys.iter().enumerate().map(|(i, key)| (key.as_bytes(), i))).unwrap();
+                assert_eq!(map.len(), keys.len());
+                assert_eq!(map.get(query.as_bytes()), expected.as_ref());
+                assert_eq!(map.contains_key(query.as_bytes()), expected.is_some());
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", None),
+
+        t2_1: (&[""], "", Some(0)),
+
+        // Level order of keys ("a", "bob", "an", "and") differs from their order.
+        t3_1: (&["a", "an", "and", "bob"], "a", Some(0)),
+        t3_2: (&["a", "an", "and", "bob"], "an", Some(1)),
+        t3_3: (&["a", "an", "and", "bob"], "and", Some(2)),
+        t3_4: (&["a", "an", "and", "bob"], "bob", Some(3)),
+        t3_5: (&["a", "an", "and", "bob"], "", None),
+        t3_6: (&["a", "an", "and", "bob"], "bo", None),
+        t3_7: (&["a", "an", "and", "bob"], "bobs", None),
+    }
+}
+
+#[cfg(test)]
+mod iter_tests {
+    use crate::LoudsMap;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, prefix, expected): (&[&str], &str, &[(&str, usize)]) = $value;
+                let map = LoudsMap::from_sorted_iter(keys.iter().enumerate().map(|(i, key)| (key.as_bytes(), i))).unwrap();
+                let expected: Vec<(Vec<u8>, &usize)> = expected.iter().map(|(key, value)| (key.as_bytes().to_vec(), value)).collect();
+                assert_eq!(map.get_by_prefix(prefix.as_bytes()).collect::<Vec<_>>(), expected);
+
+                if prefix.is_empty() {
+                    assert_eq!(map.iter().collect::<Vec<_>>(), expected);
+                    assert_eq!(map.keys().collect::<Vec<_>>(), expected.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>());
+                    assert_eq!(map.values().collect::<Vec<_>>(), expected.iter().map(|(_, value)| *value).collect::<Vec<_>>());
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", &[]),
+
+        t2_1: (&[""], "", &[("", 0)]),
+
+        t3_1: (&["a", "an", "and", "bob"], "", &[("a", 0), ("an", 1), ("and", 2), ("bob", 3)]),
+        t3_2: (&["a", "an", "and", "bob"], "an", &[("an", 1), ("and", 2)]),
+        t3_3: (&["a", "an", "and", "bob"], "b", &[("bob", 3)]),
+        t3_4: (&["a", "an", "and", "bob"], "c", &[]),
+    }
+}
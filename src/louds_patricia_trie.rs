@@ -0,0 +1,61 @@
+mod louds_patricia_trie_impl;
+mod louds_patricia_trie_search;
+
+use crate::bit_vector::BitVector;
+use crate::elias_fano::EliasFano;
+use crate::{ChildNodeIter, Louds, LoudsNodeNum};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbg, MemSize};
+
+/// Path-compressed (Patricia) trie over [Louds](struct.Louds.html).
+///
+/// Unlike [LoudsTrie](struct.LoudsTrie.html), a chain of nodes with one child and no key ending there is collapsed into one node,
+/// whose edge is labeled by a sequence of `L`. Edge labels are concatenated into one buffer in level order.
+///
+/// # Examples
+/// ```
+/// use louds_rs::LoudsPatriciaTrie;
+///
+/// let trie = LoudsPatriciaTrie::from_sorted_keys(["http://a.example/", "http://b.example/"].map(str::as_bytes)).unwrap();
+/// assert!(trie.contains(b"http://b.example/"));
+/// assert!(!trie.contains(b"http://"));
+///
+/// // Root, "http://" and the two rests.
+/// assert_eq!(trie.louds().num_nodes(), 4);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub struct LoudsPatriciaTrie<L> {
+    louds: Louds,
+
+    /// Edge labels concatenated in level order.
+    labels: Vec<L>,
+
+    /// Edge from the parent to `node_num` is labeled by `labels[label_offsets[node_num - 1]..label_offsets[node_num]]`. Root has an empty label.
+    label_offsets: EliasFano,
+
+    /// `terminals[node_num - 1]` is whether a key ends at `node_num`.
+    terminals: BitVector,
+}
+
+/// Iterator over keys starting with a prefix in lexicographic order, yielding `(key, terminal node)`.
+///
+/// Returned by [LoudsPatriciaTrie::predictive_search()](struct.LoudsPatriciaTrie.html#method.predictive_search).
+/// [next_key()](#method.next_key) lends the key from a buffer reused across results, while [Iterator::next()] clones it into a `Vec`.
+pub struct PatriciaPredictiveSearchIter<'a, L> {
+    trie: &'a LoudsPatriciaTrie<L>,
+
+    /// Key of the last visited node.
+    key: Vec<L>,
+
+    /// Children not visited yet and the length of the key, for each node on the path from the prefix node to the last visited node.
+    stack: Vec<(ChildNodeIter<'a>, usize)>,
+
+    /// The prefix node until it is visited.
+    root: Option<LoudsNodeNum>,
+}
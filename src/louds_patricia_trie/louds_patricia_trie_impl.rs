@@ -0,0 +1,314 @@
+use super::LoudsPatriciaTrie;
+use crate::bit_vector::BitVectorBuilder;
+use crate::elias_fano::EliasFano;
+use crate::louds_trie::check_sorted;
+use crate::{Louds, LoudsBuilder, LoudsError, LoudsNodeNum};
+use std::collections::VecDeque;
+
+impl<L: Ord + Clone> LoudsPatriciaTrie<L> {
+    /// Builds a trie from keys sorted in lexicographic order. Duplicate keys are stored once.
+    ///
+    /// Same as [LoudsTrie::from_sorted_keys()](struct.LoudsTrie.html#method.from_sorted_keys) except that each edge is extended
+    /// while all the keys under it share the next label and none of them ends. It takes _O(<u>total length of keys</u>)_ time.
+    ///
+    /// # Errors
+    /// [LoudsError::UnsortedKeys](enum.LoudsError.html#variant.UnsortedKeys): A key is smaller than the previous one.
+    pub fn from_sorted_keys<I>(keys: I) -> Result<Self, LoudsError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[L]>,
+    {
+        let keys: Vec<I::Item> = keys.into_iter().collect();
+        check_sorted(&keys)?;
+
+        let mut builder = LoudsBuilder::new();
+        let mut labels = Vec::new();
+        // Root has an empty label.
+        let mut label_offsets = vec![0, 0];
+        let mut terminals = BitVectorBuilder::default();
+
+        // Each node covers keys[lo..hi], which share the first `depth` labels.
+        let mut queue = VecDeque::from([(0, keys.len(), 0)]);
+        while let Some((mut lo, hi, depth)) = queue.pop_front() {
+            // Keys ending at this node come first since they are prefixes of the others.
+            terminals.push(lo < hi && keys[lo].as_ref().len() == depth);
+            while lo < hi && keys[lo].as_ref().len() == depth {
+                lo += 1;
+            }
+
+            let mut degree = 0;
+            while lo < hi {
+                let first = keys[lo].as_ref();
+                let label = &first[depth];
+                let end = lo + keys[lo..hi].partition_point(|key| &key.as_ref()[depth] <= label);
+
+                // keys[lo..end] are sorted, so they share the next label iff the first and the last do.
+                // The first is the shortest, and no key ends in the middle of the edge while it is longer.
+                let last = keys[end - 1].as_ref();
+                let mut edge_end = depth + 1;
+                while edge_end < first.len() && first[edge_end] == last[edge_end] {
+                    edge_end += 1;
+                }
+
+                labels.extend_from_slice(&first[depth..edge_end]);
+                label_offsets.push(labels.len() as u64);
+                queue.push_back((lo, end, edge_end));
+                degree += 1;
+                lo = end;
+            }
+            builder.push_node(degree).unwrap();
+        }
+
+        Ok(Self {
+            louds: builder.finish().unwrap(),
+            labels,
+            label_offsets: EliasFano::from(&label_offsets[..]),
+            terminals: terminals.build(),
+        })
+    }
+
+    /// Returns whether `key` is stored in this trie.
+    ///
+    /// It takes _O(<u>length of `key`</u> + <u>number of nodes on the path</u> * log N * log <u>max num of children a node has</u>)_ time.
+    pub fn contains(&self, key: &[L]) -> bool {
+        self.get_node(key).is_some()
+    }
+
+    /// Returns the node where `key` ends, or `None` if `key` is not stored in this trie.
+    ///
+    /// Same time complexity as [contains()](#method.contains).
+    pub fn get_node(&self, key: &[L]) -> Option<LoudsNodeNum> {
+        self.find_node(key)
+            .filter(|&(node_num, len)| len == key.len() && self.is_terminal(node_num))
+            .map(|(node_num, _)| node_num)
+    }
+
+    /// Returns the number of keys stored in this trie.
+    pub fn num_keys(&self) -> u64 {
+        self.terminals.count_ones()
+    }
+
+    /// Returns the underlying [Louds](struct.Louds.html).
+    pub fn louds(&self) -> &Louds {
+        &self.louds
+    }
+
+    /// Returns the labels of the edge from the parent to `node_num`, which is empty for root.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this trie.
+    pub fn label(&self, node_num: LoudsNodeNum) -> &[L] {
+        self.check_node_num(node_num);
+        let start = self.label_offsets.get(node_num.0 - 1) as usize;
+        let end = self.label_offsets.get(node_num.0) as usize;
+        &self.labels[start..end]
+    }
+
+    /// Returns whether a key ends at `node_num`.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this trie.
+    pub fn is_terminal(&self, node_num: LoudsNodeNum) -> bool {
+        self.check_node_num(node_num);
+        self.terminals[node_num.0 - 1]
+    }
+
+    /// Walks down along `key` and returns the first node whose key starts with `key`, with the length of its key.
+    ///
+    /// `key` may end in the middle of the edge to the returned node.
+    pub(super) fn find_node(&self, key: &[L]) -> Option<(LoudsNodeNum, usize)> {
+        let mut node_num = LoudsNodeNum(1);
+        let mut len = 0;
+        while len < key.len() {
+            node_num = self.find_child(node_num, &key[len])?;
+            let edge = self.label(node_num);
+            let rest = &key[len..];
+            let n = edge.len().min(rest.len());
+            if edge[..n] != rest[..n] {
+                return None;
+            }
+            len += edge.len();
+        }
+        Some((node_num, len))
+    }
+
+    /// Returns the child of `node_num` whose label starts with `label`.
+    ///
+    /// Children have contiguous node numbers and are sorted by the first label, which no two siblings share,
+    /// so it takes _O(log N * log <u>degree</u>)_ time by binary search.
+    fn find_child(&self, node_num: LoudsNodeNum, label: &L) -> Option<LoudsNodeNum> {
+        let mut children = self.louds.parent_to_children_indices(node_num);
+        let degree = children.len() as u64;
+        // Child at index `i` has node number `i + 1 - parent`.
+        let first_child = children.next()?.0 + 1 - node_num.0;
+        let first_label = |child: u64| &self.labels[self.label_offsets.get(child - 1) as usize];
+
+        let (mut lo, mut hi) = (first_child, first_child + degree);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if first_label(mid) < label {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        (lo < first_child + degree && first_label(lo) == label).then_some(LoudsNodeNum(lo))
+    }
+
+    fn check_node_num(&self, node_num: LoudsNodeNum) {
+        if node_num.0 == 0 || node_num.0 > self.louds.num_nodes() {
+            panic!("{}", LoudsError::NodeNotFound(node_num));
+        }
+    }
+}
+
+#[cfg(test)]
+mod from_sorted_keys_success_tests {
+    use crate::{LoudsNodeNum, LoudsPatriciaTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_lbs, expected_labels, expected_terminals): (&[&str], &str, &[&str], &[u64]) = $value;
+                let trie = LoudsPatriciaTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let expected_louds = crate::Louds::from(expected_lbs);
+
+                let n = trie.louds().num_nodes();
+                assert_eq!(n, expected_louds.num_nodes());
+                assert_eq!(trie.label(LoudsNodeNum(1)), b"");
+                for node_num in 1..=n {
+                    let node_num = LoudsNodeNum(node_num);
+                    assert_eq!(trie.louds().parent_to_children(node_num), expected_louds.parent_to_children(node_num));
+                    assert_eq!(trie.is_terminal(node_num), expected_terminals.contains(&node_num.0));
+                }
+                let labels: Vec<&[u8]> = (2..=n).map(|node_num| trie.label(LoudsNodeNum(node_num))).collect();
+                let expected_labels: Vec<&[u8]> = expected_labels.iter().map(|label| label.as_bytes()).collect();
+                assert_eq!(labels, expected_labels);
+                assert_eq!(trie.num_keys(), expected_terminals.len() as u64);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], "10_0", &[], &[]),
+        t2: (&[""], "10_0", &[], &[1]),
+        t3: (&["abc"], "10_10_0", &["abc"], &[2]),
+        t4: (&["abc", "abc"], "10_10_0", &["abc"], &[2]),
+        t5: (&["", "abc"], "10_10_0", &["abc"], &[1, 2]),
+        // (1) -ab-> (2) -c-> (3)
+        //            `--d-> (4)
+        t6: (&["abc", "abd"], "10_10_110_0_0", &["ab", "c", "d"], &[3, 4]),
+        // Chains through terminal nodes are not collapsed.
+        // (1) -a-> (2) -n-> (4) -d-> (5)
+        //  `--bob-> (3)
+        t7: (&["a", "an", "and", "bob"], "10_110_10_0_10_0", &["a", "bob", "n", "d"], &[2, 3, 4, 5]),
+        t8: (&["ab", "abcde"], "10_10_10_0", &["ab", "cde"], &[2, 3]),
+    }
+}
+
+#[cfg(test)]
+mod from_sorted_keys_failure_tests {
+    use crate::{LoudsError, LoudsPatriciaTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_err): (&[&str], LoudsError) = $value;
+                let result = LoudsPatriciaTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes()));
+                assert_eq!(result.unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&["b", "a"], LoudsError::UnsortedKeys { position: 1 }),
+        t2: (&["abc", "abd", "abc"], LoudsError::UnsortedKeys { position: 2 }),
+    }
+}
+
+#[cfg(test)]
+mod get_node_tests {
+    use crate::{LoudsNodeNum, LoudsPatriciaTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, query, expected_node): (&[&str], &str, Option<u64>) = $value;
+                let trie = LoudsPatriciaTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                assert_eq!(trie.get_node(query.as_bytes()), expected_node.map(LoudsNodeNum));
+                assert_eq!(trie.contains(query.as_bytes()), expected_node.is_some());
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", None),
+        t1_2: (&[], "a", None),
+
+        t2_1: (&[""], "", Some(1)),
+
+        t3_1: (&["abc", "abd"], "", None),
+        t3_2: (&["abc", "abd"], "a", None),
+        t3_3: (&["abc", "abd"], "ab", None),
+        t3_4: (&["abc", "abd"], "abc", Some(3)),
+        t3_5: (&["abc", "abd"], "abd", Some(4)),
+        t3_6: (&["abc", "abd"], "abe", None),
+        t3_7: (&["abc", "abd"], "abcd", None),
+        t3_8: (&["abc", "abd"], "ax", None),
+
+        t4_1: (&["a", "an", "and", "bob"], "and", Some(5)),
+        t4_2: (&["a", "an", "and", "bob"], "bob", Some(3)),
+        t4_3: (&["a", "an", "and", "bob"], "bo", None),
+        t4_4: (&["a", "an", "and", "bob"], "bobs", None),
+    }
+}
+
+#[cfg(test)]
+mod find_child_tests {
+    use crate::{LoudsNodeNum, LoudsPatriciaTrie};
+
+    #[test]
+    fn every_byte() {
+        // Every other byte is a child of root, so both hits and misses fall between siblings.
+        let keys: Vec<[u8; 2]> = (0..128).map(|b| [2 * b as u8, b'x']).collect();
+        let trie = LoudsPatriciaTrie::from_sorted_keys(&keys).unwrap();
+        for b in 0..=255u8 {
+            let expected = (b % 2 == 0).then(|| LoudsNodeNum(b as u64 / 2 + 2));
+            assert_eq!(trie.get_node(&[b, b'x']), expected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod label_failure_tests {
+    use crate::{LoudsNodeNum, LoudsPatriciaTrie};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (keys, node_num): (&[&str], u64) = $value;
+                let trie = LoudsPatriciaTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let _ = trie.label(LoudsNodeNum(node_num));
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: (&[], 0),
+        t1_2: (&[], 2),
+        t2_1: (&["a", "an", "and", "bob"], 6),
+    }
+}
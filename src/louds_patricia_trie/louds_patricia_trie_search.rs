@@ -0,0 +1,113 @@
+use super::{LoudsPatriciaTrie, PatriciaPredictiveSearchIter};
+use crate::LoudsNodeNum;
+
+impl<L: Ord + Clone> LoudsPatriciaTrie<L> {
+    /// Returns an iterator over keys starting with `prefix` (including `prefix` itself) in lexicographic order.
+    ///
+    /// Same as [LoudsTrie::predictive_search()](struct.LoudsTrie.html#method.predictive_search), while `prefix` may end in the middle of an edge.
+    pub fn predictive_search(&self, prefix: &[L]) -> PatriciaPredictiveSearchIter<'_, L> {
+        let mut key = prefix.to_vec();
+        let root = self.find_node(prefix).map(|(node_num, len)| {
+            let label = self.label(node_num);
+            key.extend_from_slice(&label[label.len() - (len - prefix.len())..]);
+            node_num
+        });
+        PatriciaPredictiveSearchIter {
+            trie: self,
+            key,
+            stack: Vec::new(),
+            root,
+        }
+    }
+}
+
+impl<'a, L: Ord + Clone> PatriciaPredictiveSearchIter<'a, L> {
+    /// Advances the iterator and returns the next key and its terminal node.
+    ///
+    /// The key is borrowed from a buffer reused across results, so no allocation happens per result.
+    pub fn next_key(&mut self) -> Option<(&[L], LoudsNodeNum)> {
+        let louds = self.trie.louds();
+        if let Some(root) = self.root.take() {
+            self.stack
+                .push((louds.parent_to_children_nodes(root), self.key.len()));
+            if self.trie.is_terminal(root) {
+                return Some((&self.key, root));
+            }
+        }
+
+        while let Some((children, len)) = self.stack.last_mut() {
+            match children.next() {
+                Some(child) => {
+                    self.key.truncate(*len);
+                    self.key.extend_from_slice(self.trie.label(child));
+                    self.stack
+                        .push((louds.parent_to_children_nodes(child), self.key.len()));
+                    if self.trie.is_terminal(child) {
+                        return Some((&self.key, child));
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, L: Ord + Clone> Iterator for PatriciaPredictiveSearchIter<'a, L> {
+    type Item = (Vec<L>, LoudsNodeNum);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_key()
+            .map(|(key, node_num)| (key.to_vec(), node_num))
+    }
+}
+
+#[cfg(test)]
+mod predictive_search_tests {
+    use crate::{LoudsNodeNum, LoudsPatriciaTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, prefix, expected): (&[&str], &str, &[(&str, u64)]) = $value;
+                let trie = LoudsPatriciaTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let expected: Vec<(Vec<u8>, LoudsNodeNum)> = expected
+                    .iter()
+                    .map(|&(key, node_num)| (key.as_bytes().to_vec(), LoudsNodeNum(node_num)))
+                    .collect();
+                assert_eq!(trie.predictive_search(prefix.as_bytes()).collect::<Vec<_>>(), expected);
+
+                // Same results by lending keys.
+                let mut iter = trie.predictive_search(prefix.as_bytes());
+                for (expected_key, expected_node_num) in &expected {
+                    assert_eq!(iter.next_key(), Some((&expected_key[..], *expected_node_num)));
+                }
+                assert_eq!(iter.next_key(), None);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", &[]),
+        t1_2: (&[], "a", &[]),
+
+        t2_1: (&[""], "", &[("", 1)]),
+
+        t3_1: (&["abc", "abd"], "", &[("abc", 3), ("abd", 4)]),
+        // Prefixes ending in the middle of an edge.
+        t3_2: (&["abc", "abd"], "a", &[("abc", 3), ("abd", 4)]),
+        t3_3: (&["abc", "abd"], "ab", &[("abc", 3), ("abd", 4)]),
+        t3_4: (&["abc", "abd"], "abd", &[("abd", 4)]),
+        t3_5: (&["abc", "abd"], "abde", &[]),
+        t3_6: (&["abc", "abd"], "ax", &[]),
+
+        t4_1: (&["a", "an", "and", "bob"], "", &[("a", 2), ("an", 4), ("and", 5), ("bob", 3)]),
+        t4_2: (&["a", "an", "and", "bob"], "an", &[("an", 4), ("and", 5)]),
+        t4_3: (&["a", "an", "and", "bob"], "bo", &[("bob", 3)]),
+        t4_4: (&["a", "an", "and", "bob"], "c", &[]),
+    }
+}
@@ -0,0 +1,156 @@
+mod louds_trie_automaton;
+mod louds_trie_fuzzy;
+mod louds_trie_id;
+mod louds_trie_impl;
+mod louds_trie_lex;
+mod louds_trie_search;
+mod louds_trie_tail;
+
+pub(crate) use louds_trie_impl::check_sorted;
+
+use crate::bit_vector::BitVector;
+use crate::elias_fano::EliasFano;
+use crate::{Automaton, ChildNodeIter, Louds, LoudsNodeNum};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbg, MemSize};
+
+/// Trie over [Louds](struct.Louds.html), whose edges are labeled by `L`.
+///
+/// Each node represents the key spelled by labels from root to it. Labels are stored in level order next to the LBS,
+/// and nodes where a key ends are marked in a terminal bit vector.
+///
+/// # Examples
+/// ```
+/// use louds_rs::{LoudsNodeNum, LoudsTrie};
+///
+/// let trie = LoudsTrie::from_sorted_keys(["a", "an", "and", "bob"].map(str::as_bytes)).unwrap();
+/// assert!(trie.contains(b"an"));
+/// assert!(!trie.contains(b"bo"));
+///
+/// // Root is node#1, and its children "a" and "b" are node#2 and node#3.
+/// assert_eq!(trie.get_node(b"a"), Some(LoudsNodeNum(2)));
+/// assert_eq!(trie.get_node(b"b"), None);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub struct LoudsTrie<L> {
+    louds: Louds,
+
+    /// `labels[node_num - 2]` is the label of the edge from the parent to `node_num`. Root has no label.
+    ///
+    /// Children of a node have contiguous node numbers, so their labels are contiguous too.
+    labels: Vec<L>,
+
+    /// `terminals[node_num - 1]` is whether a key ends at `node_num`.
+    terminals: BitVector,
+
+    /// Lexicographic rank index: `get(i)` is the number of terminal nodes in subtrees of nodes _[1, i]_ (level order).
+    ///
+    /// Built by [LoudsTrie::build_lex_index()](struct.LoudsTrie.html#method.build_lex_index).
+    lex_index: Option<EliasFano>,
+
+    /// Suffixes of keys cut off from the trie. Built by [LoudsTrie::from_sorted_keys_with_tails()](struct.LoudsTrie.html#method.from_sorted_keys_with_tails).
+    tails: Option<Tails<L>>,
+}
+
+/// How [LoudsTrie::from_sorted_keys_with_tails()](struct.LoudsTrie.html#method.from_sorted_keys_with_tails) stores tails.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TailMode {
+    /// Each tail is stored as is.
+    Plain,
+
+    /// A tail which is a suffix of another tail is stored as a slice of it, e.g. "ing" of "sing" and "ring".
+    /// Building takes _O(<u>total length of tails</u> log <u>number of tails</u>)_ more time to sort them from the end.
+    SuffixShared,
+}
+
+/// Suffixes of keys stored out of the trie, each of which belongs to a leaf reached by only one key.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub(crate) struct Tails<L> {
+    /// `has_tail[node_num - 1]` is whether `node_num` has a non-empty tail.
+    has_tail: BitVector,
+
+    /// Tail `i` (rank of the node among nodes with tails) is `labels[starts[i]..starts[i] + lens[i]]`.
+    starts: Vec<u64>,
+    lens: Vec<u64>,
+    labels: Vec<L>,
+}
+
+/// Iterator over keys starting with a prefix in lexicographic order, yielding `(key, terminal node)`.
+///
+/// Returned by [LoudsTrie::predictive_search()](struct.LoudsTrie.html#method.predictive_search).
+/// [next_key()](#method.next_key) lends the key from a buffer reused across results, while [Iterator::next()] clones it into a `Vec`.
+pub struct PredictiveSearchIter<'a, L> {
+    trie: &'a LoudsTrie<L>,
+
+    /// Key of the last visited node. Its first `prefix_len` labels are the prefix.
+    key: Vec<L>,
+    prefix_len: usize,
+
+    /// Children not visited yet, for each node on the path from the prefix node to the last visited node.
+    stack: Vec<ChildNodeIter<'a>>,
+
+    /// The prefix node until it is visited.
+    root: Option<LoudsNodeNum>,
+}
+
+/// Iterator over keys which are prefixes of a query, from the shortest, yielding `(key, terminal node)`.
+///
+/// Returned by [LoudsTrie::common_prefix_search()](struct.LoudsTrie.html#method.common_prefix_search). Keys are slices of the query, so no allocation happens.
+pub struct CommonPrefixSearchIter<'a, 'q, L> {
+    trie: &'a LoudsTrie<L>,
+    query: &'q [L],
+
+    /// Node spelled by `query[..pos]`, or `None` if the trie has no more prefixes of the query.
+    node: Option<LoudsNodeNum>,
+    pos: usize,
+}
+
+/// Iterator over keys within an edit distance of a query in lexicographic order, yielding `(key, terminal node, distance)`.
+///
+/// Returned by [LoudsTrie::fuzzy_search()](struct.LoudsTrie.html#method.fuzzy_search).
+pub struct FuzzySearchIter<'a, 'q, L> {
+    trie: &'a LoudsTrie<L>,
+    query: &'q [L],
+    max_edits: usize,
+
+    /// Key of the last visited node.
+    key: Vec<L>,
+
+    /// Levenshtein DP rows of nodes on the path to the last visited node (followed by labels of its tail), each of `query.len() + 1` elements.
+    /// `rows[d * (query.len() + 1) + j]` is the edit distance between the first `d` labels of `key` and `query[..j]`.
+    rows: Vec<usize>,
+
+    /// Children not visited yet, for each node on the path to the last visited node.
+    stack: Vec<ChildNodeIter<'a>>,
+
+    /// Root until it is visited.
+    root: Option<LoudsNodeNum>,
+}
+
+/// Iterator over keys accepted by an [Automaton](trait.Automaton.html) in lexicographic order, yielding `(key, terminal node)`.
+///
+/// Returned by [LoudsTrie::search()](struct.LoudsTrie.html#method.search).
+pub struct AutomatonSearchIter<'a, L, A: Automaton<L>> {
+    trie: &'a LoudsTrie<L>,
+    automaton: A,
+
+    /// Key of the last visited node.
+    key: Vec<L>,
+
+    /// States of the automaton at nodes on the path to the last visited node.
+    states: Vec<A::State>,
+
+    /// Children not visited yet, for each node on the path to the last visited node.
+    stack: Vec<ChildNodeIter<'a>>,
+
+    /// Root until it is visited.
+    root: Option<LoudsNodeNum>,
+}
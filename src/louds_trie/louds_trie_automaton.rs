@@ -0,0 +1,140 @@
+use super::{AutomatonSearchIter, LoudsTrie};
+use crate::{Automaton, LoudsNodeNum};
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Returns an iterator over keys accepted by `automaton` in lexicographic order.
+    ///
+    /// The automaton is run along each path from root, and a subtree is skipped as soon as its state cannot match.
+    /// It takes _O(log N)_ time plus one transition per visited node.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{Glob, LoudsTrie, Subsequence};
+    ///
+    /// let trie = LoudsTrie::from_sorted_keys(["foobar", "foobaz", "fooxbar", "qux"].map(str::as_bytes)).unwrap();
+    ///
+    /// let glob = Glob::new("foo*bar").unwrap();
+    /// let keys: Vec<Vec<u8>> = trie.search(&glob).map(|(key, _)| key).collect();
+    /// assert_eq!(keys, vec![b"foobar".to_vec(), b"fooxbar".to_vec()]);
+    ///
+    /// let keys: Vec<Vec<u8>> = trie.search(Subsequence::new(b"fxr")).map(|(key, _)| key).collect();
+    /// assert_eq!(keys, vec![b"fooxbar".to_vec()]);
+    /// ```
+    pub fn search<A: Automaton<L>>(&self, automaton: A) -> AutomatonSearchIter<'_, L, A> {
+        let start = automaton.start();
+        let root = automaton.can_match(&start).then_some(LoudsNodeNum(1));
+        AutomatonSearchIter {
+            trie: self,
+            automaton,
+            key: Vec::new(),
+            states: vec![start],
+            stack: Vec::new(),
+            root,
+        }
+    }
+}
+
+impl<'a, L: Ord + Clone, A: Automaton<L>> Iterator for AutomatonSearchIter<'a, L, A> {
+    type Item = (Vec<L>, LoudsNodeNum);
+    fn next(&mut self) -> Option<Self::Item> {
+        let louds = self.trie.louds();
+        if let Some(root) = self.root.take() {
+            self.stack.push(louds.parent_to_children_nodes(root));
+            if self.trie.is_terminal(root) && self.automaton.is_match(&self.states[0]) {
+                return Some((Vec::new(), root));
+            }
+        }
+
+        while let Some(children) = self.stack.last_mut() {
+            let Some(child) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+            let depth = self.stack.len();
+            let label = self.trie.label(child).unwrap();
+            let tail = self.trie.tail(child);
+            self.states.truncate(depth);
+            let state = self.automaton.accept(&self.states[depth - 1], label);
+            // The tail is run through as if it were a chain of nodes.
+            let Some(state) = tail.iter().try_fold(state, |state, label| {
+                self.automaton
+                    .can_match(&state)
+                    .then(|| self.automaton.accept(&state, label))
+            }) else {
+                continue;
+            };
+            if !self.automaton.can_match(&state) {
+                continue;
+            }
+            self.key.truncate(depth - 1);
+            self.key.push(label.clone());
+            self.key.extend_from_slice(tail);
+            self.stack.push(louds.parent_to_children_nodes(child));
+            let is_match = self.automaton.is_match(&state);
+            self.states.push(state);
+            if self.trie.is_terminal(child) && is_match {
+                return Some((self.key.clone(), child));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod search_tests {
+    use crate::{Glob, LoudsTrie, Prefix, Subsequence};
+
+    const KEYS: &[&str] = &["", "a", "an", "and", "ant", "band", "bob"];
+
+    fn search<A: crate::Automaton<u8>>(automaton: A) -> Vec<String> {
+        let trie = LoudsTrie::from_sorted_keys(KEYS.iter().map(|key| key.as_bytes())).unwrap();
+        trie.search(automaton)
+            .map(|(key, node_num)| {
+                assert_eq!(trie.get_node(&key), Some(node_num));
+                String::from_utf8(key).unwrap()
+            })
+            .collect()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (query, expected_prefix, expected_subsequence): (&str, &[&str], &[&str]) = $value;
+                assert_eq!(search(Prefix::new(query.as_bytes())), expected_prefix);
+                assert_eq!(search(Subsequence::new(query.as_bytes())), expected_subsequence);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("", KEYS, KEYS),
+        t2: ("an", &["an", "and", "ant"], &["an", "and", "ant", "band"]),
+        t3: ("bd", &[], &["band"]),
+        t4: ("x", &[], &[]),
+    }
+
+    macro_rules! parameterized_glob_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (pattern, expected): (&str, &[&str]) = $value;
+                assert_eq!(search(&Glob::new(pattern).unwrap()), expected);
+            }
+        )*
+        }
+    }
+
+    parameterized_glob_tests! {
+        glob_1: ("", &[""]),
+        glob_2: ("*", KEYS),
+        glob_3: ("a*", &["a", "an", "and", "ant"]),
+        glob_4: ("*nd", &["and", "band"]),
+        glob_5: ("?n?", &["and", "ant"]),
+        glob_6: ("[ab]*[dt]", &["and", "ant", "band"]),
+        glob_7: ("[!a]*", &["band", "bob"]),
+    }
+}
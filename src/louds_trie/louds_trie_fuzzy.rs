@@ -0,0 +1,127 @@
+use super::{FuzzySearchIter, LoudsTrie};
+use crate::LoudsNodeNum;
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Returns an iterator over keys whose Levenshtein distance from `query` is at most `max_edits`, in lexicographic order.
+    ///
+    /// One row of the Levenshtein DP table is computed per visited node, and subtrees are pruned when every cell of the row exceeds `max_edits`.
+    /// So it takes _O(<u>length of `query`</u>)_ time per visited node, in addition to _O(log N)_ of navigation.
+    pub fn fuzzy_search<'q>(&self, query: &'q [L], max_edits: usize) -> FuzzySearchIter<'_, 'q, L> {
+        FuzzySearchIter {
+            trie: self,
+            query,
+            max_edits,
+            key: Vec::new(),
+            rows: (0..=query.len()).collect(),
+            stack: Vec::new(),
+            root: Some(LoudsNodeNum(1)),
+        }
+    }
+}
+
+impl<'a, 'q, L: Ord + Clone> FuzzySearchIter<'a, 'q, L> {
+    /// Pushes the row for the last row followed by `label`, and returns whether any of its cells is at most `max_edits`.
+    fn push_row(&mut self, label: &L) -> bool {
+        let width = self.query.len() + 1;
+        let parent_row = self.rows.len() - width;
+        self.rows.push(self.rows[parent_row] + 1);
+        for j in 1..width {
+            let substitution =
+                self.rows[parent_row + j - 1] + (self.query[j - 1] != *label) as usize;
+            let deletion = self.rows[parent_row + j] + 1;
+            let insertion = self.rows[parent_row + width + j - 1] + 1;
+            self.rows.push(substitution.min(deletion).min(insertion));
+        }
+        self.rows[parent_row + width..]
+            .iter()
+            .any(|&d| d <= self.max_edits)
+    }
+}
+
+impl<'a, 'q, L: Ord + Clone> Iterator for FuzzySearchIter<'a, 'q, L> {
+    type Item = (Vec<L>, LoudsNodeNum, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.query.len() + 1;
+        let louds = self.trie.louds();
+        if let Some(root) = self.root.take() {
+            self.stack.push(louds.parent_to_children_nodes(root));
+            if self.trie.is_terminal(root) && self.query.len() <= self.max_edits {
+                return Some((Vec::new(), root, self.query.len()));
+            }
+        }
+
+        while let Some(children) = self.stack.last_mut() {
+            let Some(child) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+            let depth = self.stack.len();
+            let trie = self.trie;
+            let label = trie.label(child).unwrap();
+            let tail = trie.tail(child);
+            self.key.truncate(depth - 1);
+            self.key.push(label.clone());
+            self.key.extend_from_slice(tail);
+
+            // Distances never decrease down the tree, so the subtree can be pruned.
+            // Labels of the tail are processed in the same way, while their rows are dropped with the leaf.
+            self.rows.truncate(depth * width);
+            if !self.push_row(label) || !tail.iter().all(|label| self.push_row(label)) {
+                continue;
+            }
+            self.stack.push(louds.parent_to_children_nodes(child));
+            let distance = self.rows[self.rows.len() - 1];
+            if self.trie.is_terminal(child) && distance <= self.max_edits {
+                return Some((self.key.clone(), child, distance));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod fuzzy_search_tests {
+    use crate::LoudsTrie;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, query, max_edits, expected): (&[&str], &str, usize, &[(&str, usize)]) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let actual: Vec<(Vec<u8>, usize)> = trie
+                    .fuzzy_search(query.as_bytes(), max_edits)
+                    .map(|(key, node_num, distance)| {
+                        assert_eq!(trie.get_node(&key), Some(node_num));
+                        (key, distance)
+                    })
+                    .collect();
+                let expected: Vec<(Vec<u8>, usize)> = expected.iter().map(|&(key, distance)| (key.as_bytes().to_vec(), distance)).collect();
+                assert_eq!(actual, expected);
+            }
+        )*
+        }
+    }
+
+    const KEYS: &[&str] = &["", "a", "an", "and", "ant", "band", "bob"];
+
+    parameterized_tests! {
+        t1_1: (&[], "", 0, &[]),
+        t1_2: (&[], "a", 3, &[]),
+
+        t2_1: (&["abc"], "abc", 0, &[("abc", 0)]),
+        t2_2: (&["abc"], "abd", 0, &[]),
+        t2_3: (&["abc"], "abd", 1, &[("abc", 1)]),
+        t2_4: (&["abc"], "ac", 1, &[("abc", 1)]),
+        t2_5: (&["abc"], "abcd", 1, &[("abc", 1)]),
+        t2_6: (&["abc"], "", 2, &[]),
+        t2_7: (&["abc"], "", 3, &[("abc", 3)]),
+
+        t3_1: (KEYS, "and", 0, &[("and", 0)]),
+        t3_2: (KEYS, "and", 1, &[("an", 1), ("and", 0), ("ant", 1), ("band", 1)]),
+        t3_3: (KEYS, "and", 2, &[("a", 2), ("an", 1), ("and", 0), ("ant", 1), ("band", 1)]),
+        t3_4: (KEYS, "", 1, &[("", 0), ("a", 1)]),
+        t3_5: (KEYS, "bad", 1, &[("band", 1)]),
+    }
+}
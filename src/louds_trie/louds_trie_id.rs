@@ -0,0 +1,111 @@
+use super::LoudsTrie;
+use crate::LoudsNodeNum;
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Returns the id of `key`, or `None` if `key` is not stored in this trie.
+    ///
+    /// Ids are ranks of terminal nodes in level order, so they are dense in _[0, [num_keys()](#method.num_keys))_.
+    /// Note that they are not in lexicographic order of keys.
+    ///
+    /// Same time complexity as [get_node()](#method.get_node) plus _O(1)_ rank.
+    pub fn key_to_id(&self, key: &[L]) -> Option<u64> {
+        self.get_node(key)
+            .map(|node_num| self.terminal_rank(node_num))
+    }
+
+    /// Returns the key of `id`, which is the inverse of [key_to_id()](#method.key_to_id).
+    ///
+    /// Labels are collected by walking up from the terminal node to root, so it takes _O(<u>length of key</u> log N)_ time.
+    ///
+    /// # Panics
+    /// `id` >= [num_keys()](#method.num_keys)
+    pub fn id_to_key(&self, id: u64) -> Vec<L> {
+        let node_num = self.id_to_node(id);
+        let mut key: Vec<L> = self
+            .louds
+            .child_to_ancestors(node_num)
+            .map(|node_num| self.labels[node_num.0 as usize - 2].clone())
+            .collect();
+        key.reverse();
+        key.extend_from_slice(self.tail(node_num));
+        key
+    }
+
+    /// Returns the terminal node of `id`.
+    ///
+    /// It takes _O(log N)_ time.
+    ///
+    /// # Panics
+    /// `id` >= [num_keys()](#method.num_keys)
+    pub fn id_to_node(&self, id: u64) -> LoudsNodeNum {
+        let index = id
+            .checked_add(1)
+            .and_then(|num| self.terminals.select(num))
+            .unwrap_or_else(|| {
+                panic!(
+                    "id={} must be less than the number of keys ({})",
+                    id,
+                    self.num_keys()
+                )
+            });
+        LoudsNodeNum(index + 1)
+    }
+}
+
+#[cfg(test)]
+mod key_to_id_tests {
+    use crate::LoudsTrie;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_ids): (&[&str], &[(&str, Option<u64>)]) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                for &(key, expected_id) in expected_ids {
+                    assert_eq!(trie.key_to_id(key.as_bytes()), expected_id);
+                    if let Some(id) = expected_id {
+                        assert_eq!(trie.id_to_key(id), key.as_bytes());
+                        assert_eq!(trie.id_to_node(id), trie.get_node(key.as_bytes()).unwrap());
+                    }
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], &[("", None)]),
+        t2: (&[""], &[("", Some(0)), ("a", None)]),
+        // Terminal nodes in level order: "a" (node#2), "an" (node#4), "and" (node#6), "bob" (node#7).
+        t3: (&["a", "an", "and", "bob"], &[("a", Some(0)), ("an", Some(1)), ("and", Some(2)), ("bob", Some(3)), ("b", None)]),
+        // "b" (node#3) comes before "ab" (node#4) in level order.
+        t4: (&["ab", "b"], &[("ab", Some(1)), ("b", Some(0))]),
+    }
+}
+
+#[cfg(test)]
+mod id_to_key_failure_tests {
+    use crate::LoudsTrie;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (keys, id): (&[&str], u64) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let _ = trie.id_to_key(id);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], 0),
+        t2: (&["a", "an", "and", "bob"], 4),
+        t3: (&["a", "an", "and", "bob"], u64::MAX),
+    }
+}
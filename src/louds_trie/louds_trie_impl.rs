@@ -0,0 +1,349 @@
+use super::{LoudsTrie, TailMode, Tails};
+use crate::bit_vector::BitVectorBuilder;
+use crate::{Louds, LoudsBuilder, LoudsError, LoudsNodeNum};
+use std::collections::VecDeque;
+
+/// Max number of children for which [LoudsTrie::find_child()] uses linear search instead of binary search.
+const LINEAR_SEARCH_MAX_DEGREE: usize = 8;
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Builds a trie from keys sorted in lexicographic order. Duplicate keys are stored once.
+    ///
+    /// Nodes are created in level order by splitting the sorted keys at each depth, so it takes _O(<u>total length of keys</u>)_ time.
+    ///
+    /// # Errors
+    /// [LoudsError::UnsortedKeys](enum.LoudsError.html#variant.UnsortedKeys): A key is smaller than the previous one.
+    pub fn from_sorted_keys<I>(keys: I) -> Result<Self, LoudsError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[L]>,
+    {
+        let keys: Vec<I::Item> = keys.into_iter().collect();
+        check_sorted(&keys)?;
+        Ok(Self::build(&keys, None, |_| {}))
+    }
+
+    /// Builds a trie from sorted `keys`, calling `on_terminal(i)` for each terminal node in level order,
+    /// where `keys[i]` is the (first) key ending at the node.
+    ///
+    /// With `tail_mode`, a node other than root reached by only one key becomes a leaf, and the rest of the key is stored as its tail.
+    pub(crate) fn build<K: AsRef<[L]>>(
+        keys: &[K],
+        tail_mode: Option<TailMode>,
+        mut on_terminal: impl FnMut(usize),
+    ) -> Self {
+        let mut builder = LoudsBuilder::new();
+        let mut labels = Vec::new();
+        let mut terminals = BitVectorBuilder::default();
+        let mut has_tail = BitVectorBuilder::default();
+        let mut tails = Vec::new();
+
+        // Each node covers keys[lo..hi], which share the first `depth` labels.
+        let mut queue = VecDeque::from([(0, keys.len(), 0)]);
+        while let Some((mut lo, hi, depth)) = queue.pop_front() {
+            // Sorted keys are all the same iff the first and the last are.
+            if tail_mode.is_some() && depth > 0 && keys[lo].as_ref() == keys[hi - 1].as_ref() {
+                let tail = &keys[lo].as_ref()[depth..];
+                terminals.push(true);
+                on_terminal(lo);
+                has_tail.push(!tail.is_empty());
+                if !tail.is_empty() {
+                    tails.push(tail);
+                }
+                builder.push_node(0).unwrap();
+                continue;
+            }
+            has_tail.push(false);
+
+            // Keys ending at this node come first since they are prefixes of the others.
+            let is_terminal = lo < hi && keys[lo].as_ref().len() == depth;
+            terminals.push(is_terminal);
+            if is_terminal {
+                on_terminal(lo);
+            }
+            while lo < hi && keys[lo].as_ref().len() == depth {
+                lo += 1;
+            }
+
+            let mut degree = 0;
+            while lo < hi {
+                let label = &keys[lo].as_ref()[depth];
+                let end = lo + keys[lo..hi].partition_point(|key| &key.as_ref()[depth] <= label);
+                labels.push(label.clone());
+                queue.push_back((lo, end, depth + 1));
+                degree += 1;
+                lo = end;
+            }
+            builder.push_node(degree).unwrap();
+        }
+
+        Self {
+            louds: builder.finish().unwrap(),
+            labels,
+            terminals: terminals.build(),
+            lex_index: None,
+            tails: tail_mode.map(|mode| Tails::new(has_tail.build(), &tails, mode)),
+        }
+    }
+
+    /// Returns whether `key` is stored in this trie.
+    ///
+    /// It takes _O(<u>length of `key`</u> * (log N + log <u>max num of children a node has</u>))_ time.
+    pub fn contains(&self, key: &[L]) -> bool {
+        self.get_node(key).is_some()
+    }
+
+    /// Returns the node where `key` ends, or `None` if `key` is not stored in this trie.
+    ///
+    /// Same time complexity as [contains()](#method.contains).
+    pub fn get_node(&self, key: &[L]) -> Option<LoudsNodeNum> {
+        self.find_node(key)
+            .filter(|&(node_num, depth)| {
+                self.is_terminal(node_num) && self.tail(node_num) == &key[depth..]
+            })
+            .map(|(node_num, _)| node_num)
+    }
+
+    /// Returns the number of keys stored in this trie.
+    pub fn num_keys(&self) -> u64 {
+        self.terminals.count_ones()
+    }
+
+    /// Returns the underlying [Louds](struct.Louds.html).
+    pub fn louds(&self) -> &Louds {
+        &self.louds
+    }
+
+    /// Returns the label of the edge from the parent to `node_num`, or `None` for root.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this trie.
+    pub fn label(&self, node_num: LoudsNodeNum) -> Option<&L> {
+        self.check_node_num(node_num);
+        node_num.0.checked_sub(2).map(|i| &self.labels[i as usize])
+    }
+
+    /// Returns whether a key ends at `node_num`.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this trie.
+    pub fn is_terminal(&self, node_num: LoudsNodeNum) -> bool {
+        self.check_node_num(node_num);
+        self.terminals[node_num.0 - 1]
+    }
+
+    /// Returns the node spelled by `key` whether or not a key ends there, with its depth.
+    ///
+    /// If the walk reaches a node with a tail before the end of `key`, the node is returned when the rest of `key` is a prefix of the tail.
+    pub(super) fn find_node(&self, key: &[L]) -> Option<(LoudsNodeNum, usize)> {
+        let mut node_num = LoudsNodeNum(1);
+        for (depth, label) in key.iter().enumerate() {
+            let tail = self.tail(node_num);
+            if !tail.is_empty() {
+                return tail.starts_with(&key[depth..]).then_some((node_num, depth));
+            }
+            node_num = self.find_child(node_num, label)?;
+        }
+        Some((node_num, key.len()))
+    }
+
+    /// Returns the child of `node_num` whose label is `label`.
+    ///
+    /// Children have contiguous node numbers and are sorted by label, so it searches the slice of their labels:
+    /// linearly for up to [LINEAR_SEARCH_MAX_DEGREE] children and by binary search otherwise.
+    /// It takes _O(log N + log <u>degree</u>)_ time.
+    pub(super) fn find_child(&self, node_num: LoudsNodeNum, label: &L) -> Option<LoudsNodeNum> {
+        let mut children = self.louds.parent_to_children_indices(node_num);
+        let degree = children.len();
+        let first_child_index = children.next()?;
+        // Child at index `i` has node number `i + 1 - parent`.
+        let first_child = first_child_index.0 + 1 - node_num.0;
+        let start = first_child as usize - 2;
+        let siblings = &self.labels[start..start + degree];
+
+        let pos = if degree <= LINEAR_SEARCH_MAX_DEGREE {
+            // Counts smaller labels without breaking at the first match, which compiles to branchless code.
+            siblings.iter().filter(|&sibling| sibling < label).count()
+        } else {
+            siblings.partition_point(|sibling| sibling < label)
+        };
+        (siblings.get(pos) == Some(label)).then_some(LoudsNodeNum(first_child + pos as u64))
+    }
+
+    /// Returns the rank (0-origin) of terminal `node_num` among terminal nodes in level order.
+    pub(crate) fn terminal_rank(&self, node_num: LoudsNodeNum) -> u64 {
+        self.terminals.rank(node_num.0 - 1) - 1
+    }
+
+    pub(super) fn check_node_num(&self, node_num: LoudsNodeNum) {
+        if node_num.0 == 0 || node_num.0 > self.louds.num_nodes() {
+            panic!("{}", LoudsError::NodeNotFound(node_num));
+        }
+    }
+}
+
+/// Checks that `keys` are sorted in lexicographic order.
+///
+/// # Errors
+/// [LoudsError::UnsortedKeys](enum.LoudsError.html#variant.UnsortedKeys): A key is smaller than the previous one.
+pub(crate) fn check_sorted<L: Ord, K: AsRef<[L]>>(keys: &[K]) -> Result<(), LoudsError> {
+    match (1..keys.len()).find(|&i| keys[i].as_ref() < keys[i - 1].as_ref()) {
+        Some(position) => Err(LoudsError::UnsortedKeys { position }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod from_sorted_keys_success_tests {
+    use crate::{LoudsNodeNum, LoudsTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_lbs, expected_labels, expected_terminals): (&[&str], &str, &str, &[u64]) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let expected_louds = crate::Louds::from(expected_lbs);
+
+                let n = trie.louds().num_nodes();
+                assert_eq!(n, expected_louds.num_nodes());
+                assert_eq!(trie.label(LoudsNodeNum(1)), None);
+                for node_num in 1..=n {
+                    let node_num = LoudsNodeNum(node_num);
+                    assert_eq!(trie.louds().parent_to_children(node_num), expected_louds.parent_to_children(node_num));
+                    assert_eq!(trie.is_terminal(node_num), expected_terminals.contains(&node_num.0));
+                }
+                let labels: Vec<u8> = (2..=n).map(|node_num| *trie.label(LoudsNodeNum(node_num)).unwrap()).collect();
+                assert_eq!(labels, expected_labels.as_bytes());
+                assert_eq!(trie.num_keys(), expected_terminals.len() as u64);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], "10_0", "", &[]),
+        t2: (&[""], "10_0", "", &[1]),
+        t3: (&["a"], "10_10_0", "a", &[2]),
+        t4: (&["a", "a"], "10_10_0", "a", &[2]),
+        t5: (&["", "a", "ab"], "10_10_10_0", "ab", &[1, 2, 3]),
+        // (1) -a-> (2) -n-> (4) -d-> (6)
+        //  `--b-> (3) -o-> (5) -b-> (7)
+        t6: (&["a", "an", "and", "bob"], "10_110_10_10_10_10_0_0", "abnodb", &[2, 4, 6, 7]),
+    }
+}
+
+#[cfg(test)]
+mod from_sorted_keys_failure_tests {
+    use crate::{LoudsError, LoudsTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_err): (&[&str], LoudsError) = $value;
+                let result = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes()));
+                assert_eq!(result.unwrap_err(), expected_err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&["b", "a"], LoudsError::UnsortedKeys { position: 1 }),
+        t2: (&["a", "ab", "aa"], LoudsError::UnsortedKeys { position: 2 }),
+        t3: (&["ab", "a"], LoudsError::UnsortedKeys { position: 1 }),
+    }
+}
+
+#[cfg(test)]
+mod get_node_tests {
+    use crate::{LoudsNodeNum, LoudsTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, query, expected_node): (&[&str], &str, Option<u64>) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                assert_eq!(trie.get_node(query.as_bytes()), expected_node.map(LoudsNodeNum));
+                assert_eq!(trie.contains(query.as_bytes()), expected_node.is_some());
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", None),
+        t1_2: (&[], "a", None),
+
+        t2_1: (&[""], "", Some(1)),
+
+        t3_1: (&["a", "an", "and", "bob"], "", None),
+        t3_2: (&["a", "an", "and", "bob"], "a", Some(2)),
+        t3_3: (&["a", "an", "and", "bob"], "an", Some(4)),
+        t3_4: (&["a", "an", "and", "bob"], "and", Some(6)),
+        t3_5: (&["a", "an", "and", "bob"], "bob", Some(7)),
+        t3_6: (&["a", "an", "and", "bob"], "bo", None),
+        t3_7: (&["a", "an", "and", "bob"], "andy", None),
+        t3_8: (&["a", "an", "and", "bob"], "c", None),
+    }
+}
+
+#[cfg(test)]
+mod label_failure_tests {
+    use crate::{LoudsNodeNum, LoudsTrie};
+
+    macro_rules! parameterized_node_not_found_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            #[should_panic]
+            fn $name() {
+                let (keys, node_num): (&[&str], u64) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let _ = trie.label(LoudsNodeNum(node_num));
+            }
+        )*
+        }
+    }
+
+    parameterized_node_not_found_tests! {
+        t1_1: (&[], 0),
+        t1_2: (&[], 2),
+        t2_1: (&["a", "an", "and", "bob"], 8),
+    }
+}
+
+#[cfg(test)]
+mod find_child_tests {
+    use crate::{LoudsNodeNum, LoudsTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                // Every other byte below `2 * fanout` is a child of root, so both hits and misses fall between siblings.
+                let fanout: u16 = $value;
+                let keys: Vec<[u8; 1]> = (0..fanout).map(|b| [(2 * b) as u8]).collect();
+                let trie = LoudsTrie::from_sorted_keys(&keys).unwrap();
+                for b in 0..=255u8 {
+                    let expected = (b % 2 == 0 && (b as u16) < 2 * fanout).then(|| LoudsNodeNum(b as u64 / 2 + 2));
+                    assert_eq!(trie.get_node(&[b]), expected);
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: 1,
+        t2: 8,
+        t3: 9,
+        t4: 128,
+    }
+}
@@ -0,0 +1,337 @@
+use super::{LoudsTrie, PredictiveSearchIter};
+use crate::elias_fano::EliasFano;
+use crate::LoudsNodeNum;
+use std::cmp::Ordering;
+use std::iter::Take;
+use std::ops::{Bound, Range, RangeBounds};
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Returns the number of keys smaller than `key`, i.e. the lexicographic rank of the first key _>= `key`_.
+    ///
+    /// Lexicographic ranks are in _[0, [num_keys()](#method.num_keys))_, unlike ids by [key_to_id()](#method.key_to_id) which are in level order.
+    ///
+    /// It takes _O(<u>length of `key`</u> log N)_ time with the lexicographic rank index, or _O(<u>length of `key`</u> * <u>height</u> log N)_ time without it.
+    pub fn lower_bound(&self, key: &[L]) -> u64 {
+        self.lower_bound_node(key).0
+    }
+
+    /// Returns the lexicographic rank of `key`, or `None` if `key` is not stored in this trie.
+    ///
+    /// Same time complexity as [lower_bound()](#method.lower_bound).
+    pub fn rank_of(&self, key: &[L]) -> Option<u64> {
+        match self.lower_bound_node(key) {
+            (rank, Some(node_num)) if self.is_terminal(node_num) => Some(rank),
+            _ => None,
+        }
+    }
+
+    /// Returns the `k`-th (0-origin) smallest key, or `None` if _`k` >= [num_keys()](#method.num_keys)_.
+    ///
+    /// It takes _O(<u>length of the key</u> log N log <u>max num of children a node has</u>)_ time with the lexicographic rank index.
+    pub fn nth_key(&self, k: u64) -> Option<Vec<L>> {
+        if k >= self.num_keys() {
+            return None;
+        }
+
+        let (mut k, mut node_num, mut key) = (k, 1, Vec::new());
+        loop {
+            if self.terminals[node_num - 1] {
+                if k == 0 {
+                    key.extend_from_slice(self.tail(LoudsNodeNum(node_num)));
+                    return Some(key);
+                }
+                k -= 1;
+            }
+
+            // The child whose subtree contains the `k`-th key.
+            let children = self.children_range(node_num);
+            let (mut lo, mut hi) = (children.start, children.end);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if self.terminals_in_subtrees(children.start..mid + 1) <= k {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            k -= self.terminals_in_subtrees(children.start..lo);
+            key.push(self.labels[lo as usize - 2].clone());
+            node_num = lo;
+        }
+    }
+
+    /// Returns an iterator over keys in `range` in lexicographic order, yielding `(key, terminal node)`.
+    ///
+    /// It takes _O(<u>length of bounds</u>)_ child lookups plus [lower_bound()](#method.lower_bound) for each bound to start, then _O(log N)_ time per visited node.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::LoudsTrie;
+    ///
+    /// let trie = LoudsTrie::from_sorted_keys(["a", "an", "and", "bob"].map(str::as_bytes)).unwrap();
+    /// let keys: Vec<Vec<u8>> = trie.range(&b"an"[..]..&b"b"[..]).map(|(key, _)| key).collect();
+    /// assert_eq!(keys, vec![b"an".to_vec(), b"and".to_vec()]);
+    /// ```
+    pub fn range<'k, R>(&self, range: R) -> Take<PredictiveSearchIter<'_, L>>
+    where
+        R: RangeBounds<&'k [L]>,
+        L: 'k,
+    {
+        let (start, iter) = match range.start_bound() {
+            Bound::Included(key) => (self.lower_bound(key), self.seek(key)),
+            Bound::Excluded(key) => {
+                let mut iter = self.seek(key);
+                match self.rank_of(key) {
+                    Some(rank) => {
+                        iter.next_key();
+                        (rank + 1, iter)
+                    }
+                    None => (self.lower_bound(key), iter),
+                }
+            }
+            Bound::Unbounded => (0, self.seek(&[])),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.lower_bound(key) + self.contains(key) as u64,
+            Bound::Excluded(key) => self.lower_bound(key),
+            Bound::Unbounded => self.num_keys(),
+        };
+        iter.take(end.saturating_sub(start) as usize)
+    }
+
+    /// Builds the lexicographic rank index, which makes [lower_bound()](#method.lower_bound), [rank_of()](#method.rank_of), [nth_key()](#method.nth_key) and [range()](#method.range) faster.
+    ///
+    /// The index holds prefix sums of subtree terminal counts in level order, compressed by Elias-Fano encoding into
+    /// _2 + log(<u>total length of keys</u> / N)_ bits per node, since the last sum counts each key once per node on its path.
+    /// It is about 2 bits when keys share few prefixes, and at most _2 + log(<u>average key length</u>)_ bits when they share long ones.
+    /// It takes _O(N log N)_ time.
+    pub fn build_lex_index(&mut self) {
+        let n = self.louds.num_nodes();
+        // counts[v] = number of terminal nodes in the subtree of `v`, accumulated from leaves since parents precede children in level order.
+        let mut counts = vec![0u64; n as usize + 1];
+        for v in (1..=n).rev() {
+            counts[v as usize] += self.terminals[v - 1] as u64;
+            if v > 1 {
+                let index = self.louds.node_num_to_index(LoudsNodeNum(v));
+                let parent = self.louds.child_to_parent(index);
+                counts[parent.0 as usize] += counts[v as usize];
+            }
+        }
+        for v in 1..=n as usize {
+            counts[v] += counts[v - 1];
+        }
+        self.lex_index = Some(EliasFano::from(&counts[..]));
+    }
+
+    /// Drops the lexicographic rank index.
+    pub fn drop_lex_index(&mut self) {
+        self.lex_index = None;
+    }
+
+    /// Returns whether this trie has the lexicographic rank index.
+    pub fn has_lex_index(&self) -> bool {
+        self.lex_index.is_some()
+    }
+
+    /// Returns [lower_bound()](#method.lower_bound) of `key`, and the node spelled by `key` if any.
+    fn lower_bound_node(&self, key: &[L]) -> (u64, Option<LoudsNodeNum>) {
+        let (mut rank, mut node_num) = (0, 1);
+        for depth in 0..=key.len() {
+            // A node with a tail is a leaf holding one key, which is compared with `key` by the tail and the rest of `key`.
+            let tail = self.tail(LoudsNodeNum(node_num));
+            if !tail.is_empty() {
+                return match tail.cmp(&key[depth..]) {
+                    Ordering::Less => (rank + 1, None),
+                    Ordering::Equal => (rank, Some(LoudsNodeNum(node_num))),
+                    Ordering::Greater => (rank, None),
+                };
+            }
+            let Some(label) = key.get(depth) else {
+                break;
+            };
+
+            // The key of `node_num` is a proper prefix of `key`, so smaller than it.
+            rank += self.terminals[node_num - 1] as u64;
+
+            let children = self.children_range(node_num);
+            let labels = &self.labels[children.start as usize - 2..children.end as usize - 2];
+            let pos = labels.partition_point(|l| l < label);
+            let child = children.start + pos as u64;
+            rank += self.terminals_in_subtrees(children.start..child);
+            if labels.get(pos) != Some(label) {
+                return (rank, None);
+            }
+            node_num = child;
+        }
+        (rank, Some(LoudsNodeNum(node_num)))
+    }
+
+    /// Returns an iterator whose first key is the smallest key _>= `key`_.
+    fn seek(&self, key: &[L]) -> PredictiveSearchIter<'_, L> {
+        let mut iter = PredictiveSearchIter {
+            trie: self,
+            key: Vec::with_capacity(key.len()),
+            prefix_len: 0,
+            stack: Vec::with_capacity(key.len() + 1),
+            root: None,
+        };
+        let mut node_num = 1;
+        for (depth, label) in key.iter().enumerate() {
+            // The key of a node with a tail is smaller than `key` only if the tail is smaller than the rest of `key`,
+            // and then the node is already skipped by the iterator of its siblings.
+            let tail = self.tail(LoudsNodeNum(node_num));
+            if !tail.is_empty() {
+                if tail < &key[depth..] {
+                    return iter;
+                }
+                break;
+            }
+            let children = self.children_range(node_num);
+            let labels = &self.labels[children.start as usize - 2..children.end as usize - 2];
+            let pos = labels.partition_point(|l| l < label);
+
+            // Siblings on the left (and the child itself if it matches) have smaller keys.
+            let mut siblings = self.louds.parent_to_children_nodes(LoudsNodeNum(node_num));
+            if labels.get(pos) == Some(label) {
+                siblings.nth(pos);
+                iter.stack.push(siblings);
+                iter.key.push(label.clone());
+                node_num = children.start + pos as u64;
+            } else {
+                if pos > 0 {
+                    siblings.nth(pos - 1);
+                }
+                iter.stack.push(siblings);
+                return iter;
+            }
+        }
+        iter.root = Some(LoudsNodeNum(node_num));
+        iter
+    }
+
+    /// Returns the range of node numbers of children of `node_num`.
+    fn children_range(&self, node_num: u64) -> Range<u64> {
+        let children = self
+            .louds
+            .children_range(LoudsNodeNum(node_num)..LoudsNodeNum(node_num + 1));
+        children.start.0..children.end.0
+    }
+
+    /// Returns the number of terminal nodes in subtrees of contiguous siblings `nodes`.
+    fn terminals_in_subtrees(&self, nodes: Range<u64>) -> u64 {
+        if nodes.is_empty() {
+            return 0;
+        }
+        match &self.lex_index {
+            Some(lex_index) => lex_index.get(nodes.end - 1) - lex_index.get(nodes.start - 1),
+            None => {
+                // Descendants of contiguous siblings are contiguous in each level.
+                let (mut count, mut nodes) =
+                    (0, LoudsNodeNum(nodes.start)..LoudsNodeNum(nodes.end));
+                while nodes.start < nodes.end {
+                    count +=
+                        self.terminals_before(nodes.end.0) - self.terminals_before(nodes.start.0);
+                    nodes = self.louds.children_range(nodes);
+                }
+                count
+            }
+        }
+    }
+
+    /// Returns the number of terminal nodes before `node_num` in level order.
+    fn terminals_before(&self, node_num: u64) -> u64 {
+        match node_num {
+            0 | 1 => 0,
+            _ => self.terminals.rank(node_num - 2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod lex_rank_tests {
+    use crate::LoudsTrie;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, queries): (&[&str], &[(&str, u64)]) = $value;
+                let mut trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                trie.build_lex_index();
+                for _ in 0..2 {
+                    for (k, key) in keys.iter().enumerate() {
+                        assert_eq!(trie.nth_key(k as u64), Some(key.as_bytes().to_vec()));
+                        assert_eq!(trie.rank_of(key.as_bytes()), Some(k as u64));
+                    }
+                    assert_eq!(trie.nth_key(keys.len() as u64), None);
+                    for &(query, expected_lower_bound) in queries {
+                        assert_eq!(trie.lower_bound(query.as_bytes()), expected_lower_bound);
+                        assert_eq!(trie.rank_of(query.as_bytes()), None);
+                    }
+
+                    // Same results without the index.
+                    trie.drop_lex_index();
+                    assert!(!trie.has_lex_index());
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], &[("", 0), ("a", 0)]),
+        t2: (&[""], &[("a", 1)]),
+        t3: (&["a", "an", "and", "bob"], &[("", 0), ("0", 0), ("aa", 1), ("ana", 2), ("andy", 3), ("b", 3), ("bobs", 4), ("c", 4)]),
+        t4: (&["ab", "b", "ba", "bb"], &[("a", 0), ("aa", 0), ("abc", 1), ("bab", 3), ("bc", 4)]),
+    }
+}
+
+#[cfg(test)]
+mod range_tests {
+    use crate::LoudsTrie;
+    use std::ops::Bound;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, start, end, expected): (&[&str], Bound<&str>, Bound<&str>, &[&str]) = $value;
+                let mut trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                trie.build_lex_index();
+                let bounds = (start.map(str::as_bytes), end.map(str::as_bytes));
+                let expected: Vec<Vec<u8>> = expected.iter().map(|key| key.as_bytes().to_vec()).collect();
+                for _ in 0..2 {
+                    let actual: Vec<Vec<u8>> = trie.range(bounds).map(|(key, _)| key).collect();
+                    assert_eq!(actual, expected);
+
+                    // Same results without the index.
+                    trie.drop_lex_index();
+                }
+            }
+        )*
+        }
+    }
+
+    const KEYS: &[&str] = &["a", "an", "and", "bob"];
+
+    parameterized_tests! {
+        t1_1: (&[], Bound::Unbounded, Bound::Unbounded, &[]),
+        t1_2: (&[], Bound::Included("a"), Bound::Included("b"), &[]),
+
+        t2_1: (&[""], Bound::Unbounded, Bound::Unbounded, &[""]),
+        t2_2: (&[""], Bound::Excluded(""), Bound::Unbounded, &[]),
+
+        t3_1: (KEYS, Bound::Unbounded, Bound::Unbounded, &["a", "an", "and", "bob"]),
+        t3_2: (KEYS, Bound::Included("an"), Bound::Excluded("bob"), &["an", "and"]),
+        t3_3: (KEYS, Bound::Excluded("an"), Bound::Included("bob"), &["and", "bob"]),
+        t3_4: (KEYS, Bound::Included("am"), Bound::Excluded("ao"), &["an", "and"]),
+        t3_5: (KEYS, Bound::Excluded("ana"), Bound::Unbounded, &["and", "bob"]),
+        t3_6: (KEYS, Bound::Included("b"), Bound::Included("bo"), &[]),
+        t3_7: (KEYS, Bound::Included("c"), Bound::Unbounded, &[]),
+        t3_8: (KEYS, Bound::Unbounded, Bound::Excluded("a"), &[]),
+        t3_9: (KEYS, Bound::Included("bob"), Bound::Included("a"), &[]),
+        t3_10: (KEYS, Bound::Included(""), Bound::Included("and"), &["a", "an", "and"]),
+    }
+}
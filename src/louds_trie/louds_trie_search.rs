@@ -0,0 +1,198 @@
+use super::{CommonPrefixSearchIter, LoudsTrie, PredictiveSearchIter};
+use crate::LoudsNodeNum;
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Returns an iterator over keys starting with `prefix` (including `prefix` itself) in lexicographic order.
+    ///
+    /// Children of a node are sorted by label, so depth-first traversal from the node of `prefix` visits keys in lexicographic order.
+    /// It takes _O(<u>length of `prefix`</u>)_ node lookups to find the start, then _O(log N)_ time per visited node.
+    pub fn predictive_search(&self, prefix: &[L]) -> PredictiveSearchIter<'_, L> {
+        // `prefix` may end in the middle of a tail, which is appended when the node is visited.
+        let (root, depth) = match self.find_node(prefix) {
+            Some((node_num, depth)) => (Some(node_num), depth),
+            None => (None, prefix.len()),
+        };
+        PredictiveSearchIter {
+            trie: self,
+            key: prefix[..depth].to_vec(),
+            prefix_len: depth,
+            stack: Vec::new(),
+            root,
+        }
+    }
+
+    /// Returns an iterator over keys which are prefixes of `query` (including `query` itself), from the shortest.
+    ///
+    /// It walks down the trie along `query` only once, so it takes _O(<u>length of `query`</u>)_ child lookups in total.
+    pub fn common_prefix_search<'q>(&self, query: &'q [L]) -> CommonPrefixSearchIter<'_, 'q, L> {
+        CommonPrefixSearchIter {
+            trie: self,
+            query,
+            node: Some(LoudsNodeNum(1)),
+            pos: 0,
+        }
+    }
+
+    /// Returns the longest key which is a prefix of `query`, and its terminal node.
+    ///
+    /// Same time complexity as [common_prefix_search()](#method.common_prefix_search).
+    pub fn longest_prefix_match<'q>(&self, query: &'q [L]) -> Option<(&'q [L], LoudsNodeNum)> {
+        self.common_prefix_search(query).last()
+    }
+}
+
+impl<'a, L: Ord + Clone> PredictiveSearchIter<'a, L> {
+    /// Advances the iterator and returns the next key and its terminal node.
+    ///
+    /// The key is borrowed from a buffer reused across results, so no allocation happens per result.
+    pub fn next_key(&mut self) -> Option<(&[L], LoudsNodeNum)> {
+        let louds = self.trie.louds();
+        if let Some(root) = self.root.take() {
+            self.stack.push(louds.parent_to_children_nodes(root));
+            if self.trie.is_terminal(root) {
+                self.key.extend_from_slice(self.trie.tail(root));
+                return Some((&self.key, root));
+            }
+        }
+
+        while let Some(children) = self.stack.last_mut() {
+            match children.next() {
+                Some(child) => {
+                    self.key.truncate(self.prefix_len + self.stack.len() - 1);
+                    self.key.push(self.trie.label(child).unwrap().clone());
+                    self.stack.push(louds.parent_to_children_nodes(child));
+                    if self.trie.is_terminal(child) {
+                        self.key.extend_from_slice(self.trie.tail(child));
+                        return Some((&self.key, child));
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, L: Ord + Clone> Iterator for PredictiveSearchIter<'a, L> {
+    type Item = (Vec<L>, LoudsNodeNum);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_key()
+            .map(|(key, node_num)| (key.to_vec(), node_num))
+    }
+}
+
+impl<'a, 'q, L: Ord + Clone> Iterator for CommonPrefixSearchIter<'a, 'q, L> {
+    type Item = (&'q [L], LoudsNodeNum);
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node_num) = self.node {
+            let mut len = self.pos;
+            let tail = self.trie.tail(node_num);
+            if tail.is_empty() {
+                self.node = self
+                    .query
+                    .get(self.pos)
+                    .and_then(|label| self.trie.find_child(node_num, label));
+                self.pos += 1;
+            } else {
+                // Nodes with tails are leaves.
+                self.node = None;
+                if !self.query[len..].starts_with(tail) {
+                    return None;
+                }
+                len += tail.len();
+            }
+            if self.trie.is_terminal(node_num) {
+                return Some((&self.query[..len], node_num));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod predictive_search_tests {
+    use crate::{LoudsNodeNum, LoudsTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, prefix, expected): (&[&str], &str, &[(&str, u64)]) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let expected: Vec<(Vec<u8>, LoudsNodeNum)> = expected
+                    .iter()
+                    .map(|&(key, node_num)| (key.as_bytes().to_vec(), LoudsNodeNum(node_num)))
+                    .collect();
+                assert_eq!(trie.predictive_search(prefix.as_bytes()).collect::<Vec<_>>(), expected);
+
+                // Same results by lending keys.
+                let mut iter = trie.predictive_search(prefix.as_bytes());
+                for (expected_key, expected_node_num) in &expected {
+                    assert_eq!(iter.next_key(), Some((&expected_key[..], *expected_node_num)));
+                }
+                assert_eq!(iter.next_key(), None);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", &[]),
+        t1_2: (&[], "a", &[]),
+
+        t2_1: (&[""], "", &[("", 1)]),
+
+        t3_1: (&["a", "an", "and", "bob"], "", &[("a", 2), ("an", 4), ("and", 6), ("bob", 7)]),
+        t3_2: (&["a", "an", "and", "bob"], "a", &[("a", 2), ("an", 4), ("and", 6)]),
+        t3_3: (&["a", "an", "and", "bob"], "an", &[("an", 4), ("and", 6)]),
+        t3_4: (&["a", "an", "and", "bob"], "b", &[("bob", 7)]),
+        t3_5: (&["a", "an", "and", "bob"], "bob", &[("bob", 7)]),
+        t3_6: (&["a", "an", "and", "bob"], "bobs", &[]),
+        t3_7: (&["a", "an", "and", "bob"], "c", &[]),
+
+        // Level order differs from lexicographic order: "ab" (node#4) < "b" (node#3).
+        t4_1: (&["ab", "b"], "", &[("ab", 4), ("b", 3)]),
+    }
+}
+
+#[cfg(test)]
+mod common_prefix_search_tests {
+    use crate::{LoudsNodeNum, LoudsTrie};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, query, expected): (&[&str], &str, &[(&str, u64)]) = $value;
+                let trie = LoudsTrie::from_sorted_keys(keys.iter().map(|key| key.as_bytes())).unwrap();
+                let expected: Vec<(&[u8], LoudsNodeNum)> = expected
+                    .iter()
+                    .map(|&(key, node_num)| (key.as_bytes(), LoudsNodeNum(node_num)))
+                    .collect();
+                assert_eq!(trie.common_prefix_search(query.as_bytes()).collect::<Vec<_>>(), expected);
+                assert_eq!(trie.longest_prefix_match(query.as_bytes()), expected.last().copied());
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], "", &[]),
+        t1_2: (&[], "a", &[]),
+
+        t2_1: (&[""], "", &[("", 1)]),
+        t2_2: (&[""], "abc", &[("", 1)]),
+
+        t3_1: (&["a", "an", "and", "bob"], "", &[]),
+        t3_2: (&["a", "an", "and", "bob"], "a", &[("a", 2)]),
+        t3_3: (&["a", "an", "and", "bob"], "andy", &[("a", 2), ("an", 4), ("and", 6)]),
+        t3_4: (&["a", "an", "and", "bob"], "ant", &[("a", 2), ("an", 4)]),
+        t3_5: (&["a", "an", "and", "bob"], "bo", &[]),
+        t3_6: (&["a", "an", "and", "bob"], "bobby", &[("bob", 7)]),
+        t3_7: (&["a", "an", "and", "bob"], "c", &[]),
+    }
+}
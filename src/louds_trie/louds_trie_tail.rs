@@ -0,0 +1,229 @@
+use super::{LoudsTrie, TailMode, Tails};
+use crate::bit_vector::BitVector;
+use crate::louds_trie::check_sorted;
+use crate::{LoudsError, LoudsNodeNum};
+
+impl<L: Ord + Clone> LoudsTrie<L> {
+    /// Builds a trie from keys sorted in lexicographic order, cutting each key at the node where no other key shares its prefix.
+    ///
+    /// The rest of the key is stored as the tail of the node in a separate buffer instead of as a chain of nodes,
+    /// which saves most nodes of a natural-language dictionary. Every API works as with [from_sorted_keys()](#method.from_sorted_keys),
+    /// while node numbers differ.
+    ///
+    /// # Examples
+    /// ```
+    /// use louds_rs::{LoudsTrie, TailMode};
+    ///
+    /// let keys = ["bring", "sing", "string"].map(str::as_bytes);
+    /// let trie = LoudsTrie::from_sorted_keys_with_tails(keys, TailMode::SuffixShared).unwrap();
+    /// assert!(trie.contains(b"string"));
+    /// assert!(!trie.contains(b"str"));
+    ///
+    /// // Root and "b", "s", "si", "st".
+    /// assert_eq!(trie.louds().num_nodes(), 5);
+    /// let node = trie.get_node(b"string").unwrap();
+    /// assert_eq!(trie.tail(node), b"ring");
+    /// ```
+    ///
+    /// # Errors
+    /// [LoudsError::UnsortedKeys](enum.LoudsError.html#variant.UnsortedKeys): A key is smaller than the previous one.
+    pub fn from_sorted_keys_with_tails<I>(keys: I, mode: TailMode) -> Result<Self, LoudsError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[L]>,
+    {
+        let keys: Vec<I::Item> = keys.into_iter().collect();
+        check_sorted(&keys)?;
+        Ok(Self::build(&keys, Some(mode), |_| {}))
+    }
+
+    /// Returns the tail of `node_num`: labels of the key ending at `node_num` after the path from root.
+    /// It is empty unless the trie is built by [from_sorted_keys_with_tails()](#method.from_sorted_keys_with_tails).
+    ///
+    /// A node with a non-empty tail is a terminal leaf.
+    ///
+    /// # Panics
+    /// `node_num` does not exist in this trie.
+    pub fn tail(&self, node_num: LoudsNodeNum) -> &[L] {
+        self.check_node_num(node_num);
+        match &self.tails {
+            Some(tails) => tails.get(node_num),
+            None => &[],
+        }
+    }
+}
+
+impl<L: Ord + Clone> Tails<L> {
+    /// Stores `tails`, where `tails[i]` belongs to the node of the `i + 1`-th _1_ of `has_tail`.
+    pub(crate) fn new(has_tail: BitVector, tails: &[&[L]], mode: TailMode) -> Self {
+        let mut starts = vec![0; tails.len()];
+        let lens: Vec<u64> = tails.iter().map(|tail| tail.len() as u64).collect();
+        let mut labels = Vec::new();
+        match mode {
+            TailMode::Plain => {
+                for (i, tail) in tails.iter().enumerate() {
+                    starts[i] = labels.len() as u64;
+                    labels.extend_from_slice(tail);
+                }
+            }
+            TailMode::SuffixShared => {
+                // In descending order of reversed tails, a tail which is a suffix of any other is a suffix of the previous one.
+                let mut order: Vec<usize> = (0..tails.len()).collect();
+                order.sort_by(|&a, &b| tails[b].iter().rev().cmp(tails[a].iter().rev()));
+                let mut prev: Option<usize> = None;
+                for i in order {
+                    match prev {
+                        Some(p) if tails[p].ends_with(tails[i]) => {
+                            starts[i] = starts[p] + lens[p] - lens[i];
+                        }
+                        _ => {
+                            starts[i] = labels.len() as u64;
+                            labels.extend_from_slice(tails[i]);
+                        }
+                    }
+                    prev = Some(i);
+                }
+            }
+        }
+        Self {
+            has_tail,
+            starts,
+            lens,
+            labels,
+        }
+    }
+
+    /// Returns the tail of `node_num`, which is empty if it has no tail.
+    pub(crate) fn get(&self, node_num: LoudsNodeNum) -> &[L] {
+        if !self.has_tail[node_num.0 - 1] {
+            return &[];
+        }
+        let i = self.has_tail.rank(node_num.0 - 1) as usize - 1;
+        let start = self.starts[i] as usize;
+        &self.labels[start..start + self.lens[i] as usize]
+    }
+}
+
+#[cfg(test)]
+mod from_sorted_keys_with_tails_success_tests {
+    use crate::{LoudsNodeNum, LoudsTrie, TailMode};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (keys, expected_lbs, expected_labels, expected_tails, expected_terminals): (&[&str], &str, &str, &[&str], &[u64]) = $value;
+                for mode in [TailMode::Plain, TailMode::SuffixShared] {
+                    let trie = LoudsTrie::from_sorted_keys_with_tails(keys.iter().map(|key| key.as_bytes()), mode).unwrap();
+                    let expected_louds = crate::Louds::from(expected_lbs);
+
+                    let n = trie.louds().num_nodes();
+                    assert_eq!(n, expected_louds.num_nodes());
+                    for node_num in 1..=n {
+                        let node_num = LoudsNodeNum(node_num);
+                        assert_eq!(trie.louds().parent_to_children(node_num), expected_louds.parent_to_children(node_num));
+                        assert_eq!(trie.is_terminal(node_num), expected_terminals.contains(&node_num.0));
+                        assert_eq!(trie.tail(node_num), expected_tails[node_num.0 as usize - 1].as_bytes());
+                    }
+                    let labels: Vec<u8> = (2..=n).map(|node_num| *trie.label(LoudsNodeNum(node_num)).unwrap()).collect();
+                    assert_eq!(labels, expected_labels.as_bytes());
+                    assert_eq!(trie.num_keys(), expected_terminals.len() as u64);
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], "10_0", "", &[""], &[]),
+        t2: (&[""], "10_0", "", &[""], &[1]),
+        // Root never has a tail.
+        t3: (&["abc"], "10_10_0", "a", &["", "bc"], &[2]),
+        t4: (&["abc", "abc"], "10_10_0", "a", &["", "bc"], &[2]),
+        t5: (&["", "abc"], "10_10_0", "a", &["", "bc"], &[1, 2]),
+        // (1) -a-> (2) -n-> (4) -d-> (5)
+        //  `--b-> (3) "ob"
+        t6: (&["a", "an", "and", "bob"], "10_110_10_0_10_0", "abnd", &["", "", "ob", "", ""], &[2, 3, 4, 5]),
+        // (1) -a-> (2) -b-> (3) -c-> (4)
+        //                     `--d-> (5) "ef"
+        t7: (&["abc", "abdef"], "10_10_10_110_0_0", "abcd", &["", "", "", "", "ef"], &[4, 5]),
+    }
+}
+
+#[cfg(test)]
+mod tails_new_tests {
+    use super::Tails;
+    use crate::bit_vector::BitVectorBuilder;
+    use crate::{LoudsNodeNum, TailMode};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (tails, mode, expected_labels): (&[&str], TailMode, &str) = $value;
+                let mut has_tail = BitVectorBuilder::default();
+                has_tail.push_ones(tails.len() as u64);
+                let tails: Vec<&[u8]> = tails.iter().map(|tail| tail.as_bytes()).collect();
+                let stored = Tails::new(has_tail.build(), &tails, mode);
+                assert_eq!(stored.labels, expected_labels.as_bytes());
+                for (i, tail) in tails.iter().enumerate() {
+                    assert_eq!(stored.get(LoudsNodeNum(i as u64 + 1)), *tail);
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1_1: (&[], TailMode::Plain, ""),
+        t1_2: (&[], TailMode::SuffixShared, ""),
+
+        t2_1: (&["ing", "sing", "ring"], TailMode::Plain, "ingsingring"),
+        t2_2: (&["ing", "sing", "ring"], TailMode::SuffixShared, "singring"),
+
+        // A chain of suffixes shares one copy.
+        t3_1: (&["c", "abc", "bc"], TailMode::SuffixShared, "abc"),
+        t3_2: (&["ab", "ab"], TailMode::SuffixShared, "ab"),
+        t3_3: (&["ab", "b", "cb", "a"], TailMode::SuffixShared, "cbaba"),
+    }
+}
+
+#[cfg(test)]
+mod search_with_tails_tests {
+    use crate::{LoudsNodeNum, LoudsTrie, TailMode};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                // (1) -a-> (2) -n-> (4) -d-> (5)
+                //  `--b-> (3) "ob"
+                let (query, expected_node, expected_predictive, expected_common_prefixes, expected_lower_bound): (&str, Option<u64>, &[&str], &[&str], u64) = $value;
+                let trie = LoudsTrie::from_sorted_keys_with_tails(["a", "an", "and", "bob"].map(str::as_bytes), TailMode::Plain).unwrap();
+                let query = query.as_bytes();
+
+                assert_eq!(trie.get_node(query), expected_node.map(LoudsNodeNum));
+                let predictive: Vec<Vec<u8>> = trie.predictive_search(query).map(|(key, _)| key).collect();
+                assert_eq!(predictive, expected_predictive.iter().map(|key| key.as_bytes().to_vec()).collect::<Vec<_>>());
+                let common_prefixes: Vec<&[u8]> = trie.common_prefix_search(query).map(|(key, _)| key).collect();
+                assert_eq!(common_prefixes, expected_common_prefixes.iter().map(|key| key.as_bytes()).collect::<Vec<_>>());
+                assert_eq!(trie.lower_bound(query), expected_lower_bound);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("", None, &["a", "an", "and", "bob"], &[], 0),
+        t2: ("an", Some(4), &["an", "and"], &["a", "an"], 1),
+        t3: ("b", None, &["bob"], &[], 3),
+        t4: ("bo", None, &["bob"], &[], 3),
+        t5: ("bob", Some(3), &["bob"], &["bob"], 3),
+        t6: ("bobs", None, &[], &["bob"], 4),
+        t7: ("boa", None, &[], &[], 3),
+        t8: ("boc", None, &[], &[], 4),
+    }
+}
@@ -1,4 +1,7 @@
-use louds_rs::{Louds, LoudsNodeNum};
+use louds_rs::{
+    Automaton, Glob, Louds, LoudsBuilder, LoudsMap, LoudsNodeNum, LoudsPatriciaTrie, LoudsTrie,
+    LoudsView, Prefix, Subsequence, TailMode,
+};
 
 #[test]
 fn fuzzing_test() {
@@ -29,6 +32,70 @@ fn fuzzing_test() {
 
         let n_nodes = s.len() / 2;
         let louds = Louds::from(s.as_str());
+        assert_eq!(louds.num_nodes(), n_nodes as u64);
+
+        // LBS is exported as it is given.
+        assert_eq!(louds.to_string(), s);
+        assert_eq!(Louds::from(format!("{:#}", louds).as_str()).to_string(), s);
+        assert_eq!(Louds::from(&louds.to_bool_vec()[..]).to_string(), s);
+
+        // BP and DFUDS round-trip, mapping preorder ranks to the same node numbers.
+        let (bp, id_map) = louds.to_balanced_parens();
+        let (dfuds, dfuds_id_map) = louds.to_dfuds();
+        assert_eq!(dfuds_id_map, id_map);
+        for (rank, (node_num, _)) in louds.iter_preorder().enumerate() {
+            assert_eq!(id_map.node_num(rank), node_num);
+            assert_eq!(id_map.id(node_num), rank);
+        }
+        for (converted, converted_id_map) in [
+            Louds::from_balanced_parens(&bp).unwrap(),
+            Louds::from_dfuds(&dfuds).unwrap(),
+        ] {
+            assert_eq!(converted.to_string(), s);
+            assert_eq!(converted_id_map, id_map);
+        }
+
+        // write_to() and write_compact_to() round-trip through read_from().
+        for compact in [false, true] {
+            let mut bytes = Vec::new();
+            if compact {
+                louds.write_compact_to(&mut bytes).unwrap();
+            } else {
+                louds.write_to(&mut bytes).unwrap();
+            }
+            let read = Louds::read_from(&mut &bytes[..]).unwrap();
+            assert_eq!(read.num_nodes(), louds.num_nodes());
+            for node_num in 1..=n_nodes as u64 {
+                let node_num = LoudsNodeNum(node_num);
+                assert_eq!(
+                    read.parent_to_children(node_num),
+                    louds.parent_to_children(node_num)
+                );
+                assert_eq!(read.depth(node_num), louds.depth(node_num));
+            }
+        }
+
+        // LoudsView navigates written bytes in place, once they are 8-byte aligned.
+        let mut bytes = Vec::new();
+        louds.write_to(&mut bytes).unwrap();
+        let mut buf = vec![0u8; bytes.len() + 7];
+        let offset = buf.as_ptr().align_offset(8);
+        buf[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        let view = LoudsView::from_bytes(&buf[offset..offset + bytes.len()]).unwrap();
+        assert_eq!(view.num_nodes(), louds.num_nodes());
+        assert!(louds.verify().is_ok());
+        assert!(view.verify().is_ok());
+        for node_num in 1..=n_nodes as u64 {
+            let node_num = LoudsNodeNum(node_num);
+            assert_eq!(
+                view.parent_to_children(node_num),
+                louds.parent_to_children(node_num)
+            );
+            assert_eq!(
+                view.child_to_ancestors(node_num).collect::<Vec<_>>(),
+                louds.child_to_ancestors(node_num).collect::<Vec<_>>()
+            );
+        }
 
         for raw_node_num in 1..=n_nodes {
             let node_num = LoudsNodeNum(raw_node_num as u64);
@@ -42,6 +109,463 @@ fn fuzzing_test() {
             for child_index in louds.parent_to_children(node_num) {
                 assert_eq!(louds.child_to_parent(child_index), node_num);
             }
+
+            // degree() and is_leaf() agree with parent_to_children().
+            let n_children = louds.parent_to_children(node_num).len() as u64;
+            assert_eq!(louds.degree(node_num), n_children);
+            assert_eq!(louds.is_leaf(node_num), n_children == 0);
+
+            // depth() agrees with the number of ancestors, and node_num is in the level of its depth.
+            let depth = louds.depth(node_num);
+            assert_eq!(depth, louds.child_to_ancestors(node_num).count() as u64);
+            assert!(louds.level_range(depth).contains(&node_num));
+        }
+
+        // lca(), distance() and is_ancestor() agree with ancestor chains.
+        // child_to_ancestors() yields the node itself and its ancestors except root.
+        let path_to_root = |node_num: LoudsNodeNum| -> Vec<LoudsNodeNum> {
+            louds
+                .child_to_ancestors(node_num)
+                .chain(std::iter::once(LoudsNodeNum(1)))
+                .collect()
+        };
+        for _ in 0..n_nodes {
+            let a = LoudsNodeNum(rng.gen_range(1..=n_nodes as u64));
+            let b = LoudsNodeNum(rng.gen_range(1..=n_nodes as u64));
+            let (path_a, path_b) = (path_to_root(a), path_to_root(b));
+            let lca = *path_a.iter().find(|n| path_b.contains(n)).unwrap();
+            assert_eq!(louds.lca(a, b), lca);
+            assert_eq!(
+                louds.distance(a, b),
+                louds.depth(a) + louds.depth(b) - 2 * louds.depth(lca)
+            );
+            assert_eq!(louds.is_ancestor(a, b), path_b.contains(&a));
+        }
+
+        // Traversals agree with recursive DFS over parent_to_children_nodes().
+        fn dfs(
+            louds: &Louds,
+            node_num: LoudsNodeNum,
+            depth: u64,
+            preorder: &mut Vec<(LoudsNodeNum, u64)>,
+            postorder: &mut Vec<(LoudsNodeNum, u64)>,
+        ) {
+            preorder.push((node_num, depth));
+            for child in louds.parent_to_children_nodes(node_num) {
+                dfs(louds, child, depth + 1, preorder, postorder);
+            }
+            postorder.push((node_num, depth));
+        }
+        let (mut preorder, mut postorder) = (vec![], vec![]);
+        dfs(&louds, LoudsNodeNum(1), 0, &mut preorder, &mut postorder);
+        assert_eq!(louds.iter_preorder().collect::<Vec<_>>(), preorder);
+        assert_eq!(louds.iter_postorder().collect::<Vec<_>>(), postorder);
+        let level_order: Vec<_> = louds.iter_level_order().collect();
+        assert_eq!(level_order.len(), n_nodes);
+        for (i, &(node_num, depth)) in level_order.iter().enumerate() {
+            assert_eq!(node_num, LoudsNodeNum(i as u64 + 1));
+            assert_eq!(depth, louds.depth(node_num));
+        }
+
+        let node_num = LoudsNodeNum(rng.gen_range(1..=n_nodes as u64));
+        let (mut preorder, mut postorder) = (vec![], vec![]);
+        dfs(
+            &louds,
+            node_num,
+            louds.depth(node_num),
+            &mut preorder,
+            &mut postorder,
+        );
+        assert_eq!(
+            louds.iter_subtree_preorder(node_num).collect::<Vec<_>>(),
+            preorder
+        );
+        assert_eq!(
+            louds.iter_subtree_postorder(node_num).collect::<Vec<_>>(),
+            postorder
+        );
+    }
+}
+
+#[test]
+fn from_parents_and_children_lists_fuzzing_test() {
+    use rand::prelude::*;
+
+    let samples = 100;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..samples {
+        // Node `ids[i]` gets its parent from `ids[..i]`, so `parents` is always a tree.
+        let n_nodes = rng.gen_range(1..200);
+        let mut ids: Vec<usize> = (0..n_nodes).collect();
+        ids.shuffle(&mut rng);
+        let mut parents = vec![None; n_nodes];
+        for i in 1..n_nodes {
+            parents[ids[i]] = Some(ids[rng.gen_range(0..i)]);
+        }
+        eprintln!("from_parents(): parents = {:?}", parents);
+
+        let (louds, id_map) = Louds::from_parents(&parents).unwrap();
+
+        for (id, parent) in parents.iter().enumerate() {
+            let node_num = id_map.node_num(id);
+            assert_eq!(id_map.id(node_num), id);
+
+            let index = louds.node_num_to_index(node_num);
+            match *parent {
+                None => assert_eq!(node_num, LoudsNodeNum(1)),
+                Some(parent) => {
+                    assert_eq!(louds.child_to_parent(index), id_map.node_num(parent))
+                }
+            }
+        }
+
+        // Children lists sorted by id represent the same LOUDS.
+        let mut children_lists = vec![vec![]; n_nodes];
+        for (id, parent) in parents.iter().enumerate() {
+            if let Some(parent) = *parent {
+                children_lists[parent].push(id);
+            }
+        }
+        let (_, id_map2) = Louds::from_children_lists(&children_lists).unwrap();
+        assert_eq!(id_map2, id_map);
+    }
+}
+
+#[test]
+fn deep_tree_traversal_test() {
+    // A path of 1,000,000 nodes, which would overflow the stack with recursive DFS.
+    let n_nodes = 1_000_000;
+    let mut builder = LoudsBuilder::with_capacity(n_nodes);
+    for _ in 0..n_nodes - 1 {
+        builder.push_node(1).unwrap();
+    }
+    builder.push_node(0).unwrap();
+    let louds = builder.finish().unwrap();
+
+    assert!(louds
+        .iter_preorder()
+        .enumerate()
+        .all(|(i, (node_num, depth))| node_num == LoudsNodeNum(i as u64 + 1) && depth == i as u64));
+    assert!(louds
+        .iter_postorder()
+        .enumerate()
+        .all(
+            |(i, (node_num, depth))| node_num == LoudsNodeNum(n_nodes - i as u64)
+                && depth == n_nodes - 1 - i as u64
+        ));
+    assert_eq!(
+        louds.iter_level_order().last(),
+        Some((LoudsNodeNum(n_nodes), n_nodes - 1))
+    );
+}
+
+/// Generates a sorted dictionary of up to `max_keys` random keys over a small alphabet, so that keys share prefixes.
+fn generate_keys(rng: &mut rand::rngs::ThreadRng, max_keys: usize) -> Vec<Vec<u8>> {
+    use rand::prelude::*;
+
+    let n_keys = rng.gen_range(0..=max_keys);
+    let mut keys: Vec<Vec<u8>> = (0..n_keys)
+        .map(|_| {
+            let len = rng.gen_range(0..8);
+            (0..len).map(|_| rng.gen_range(b'a'..=b'd')).collect()
+        })
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Builds a trie from `keys` with no tails, plain tails or suffix-shared tails at random, which all behave the same.
+fn build_trie(rng: &mut rand::rngs::ThreadRng, keys: &[Vec<u8>]) -> LoudsTrie<u8> {
+    use rand::prelude::*;
+
+    let mode = [None, Some(TailMode::Plain), Some(TailMode::SuffixShared)][rng.gen_range(0..3)];
+    eprintln!(
+        "from_sorted_keys(): keys = {:?}, tail mode = {:?}",
+        keys, mode
+    );
+    match mode {
+        Some(mode) => LoudsTrie::from_sorted_keys_with_tails(keys, mode).unwrap(),
+        None => LoudsTrie::from_sorted_keys(keys).unwrap(),
+    }
+}
+
+#[test]
+fn louds_trie_fuzzing_test() {
+    use rand::prelude::*;
+    use std::collections::BTreeSet;
+    use std::ops::Bound;
+
+    let samples = 100;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..samples {
+        let keys = generate_keys(&mut rng, 100);
+        let mut trie = build_trie(&mut rng, &keys);
+        if rng.gen() {
+            trie.build_lex_index();
+        }
+        let set: BTreeSet<Vec<u8>> = keys.iter().cloned().collect();
+        let sorted: Vec<&Vec<u8>> = set.iter().collect();
+        assert_eq!(trie.num_keys(), set.len() as u64);
+
+        for key in &set {
+            // Labels from root to the node, followed by the tail, spell the key.
+            let node_num = trie.get_node(key).unwrap();
+            let mut spelled: Vec<u8> = trie
+                .louds()
+                .child_to_ancestors(node_num)
+                .map(|node_num| *trie.label(node_num).unwrap())
+                .collect();
+            spelled.reverse();
+            spelled.extend_from_slice(trie.tail(node_num));
+            assert_eq!(&spelled, key);
+        }
+
+        // key_to_id() is a bijection onto 0..num_keys(), and id_to_key() is its inverse.
+        let mut ids: Vec<u64> = set.iter().map(|key| trie.key_to_id(key).unwrap()).collect();
+        for (key, &id) in set.iter().zip(&ids) {
+            assert_eq!(&trie.id_to_key(id), key);
+        }
+        ids.sort();
+        assert!(ids.into_iter().eq(0..trie.num_keys()));
+
+        // nth_key() and rank_of() follow lexicographic order.
+        for (k, key) in sorted.iter().enumerate() {
+            assert_eq!(trie.nth_key(k as u64).as_ref(), Some(*key));
+            assert_eq!(trie.rank_of(key), Some(k as u64));
+        }
+        assert_eq!(trie.nth_key(sorted.len() as u64), None);
+        for query in generate_keys(&mut rng, 100) {
+            assert_eq!(trie.contains(&query), set.contains(&query));
+
+            // predictive_search() yields keys starting with the query in lexicographic order.
+            let expected: Vec<&Vec<u8>> =
+                set.iter().filter(|key| key.starts_with(&query)).collect();
+            let actual: Vec<Vec<u8>> = trie.predictive_search(&query).map(|(key, _)| key).collect();
+            assert_eq!(actual.iter().collect::<Vec<_>>(), expected);
+
+            // common_prefix_search() yields keys which are prefixes of the query, from the shortest.
+            let expected: Vec<&[u8]> = (0..=query.len())
+                .map(|len| &query[..len])
+                .filter(|prefix| set.contains(*prefix))
+                .collect();
+            let actual: Vec<&[u8]> = trie
+                .common_prefix_search(&query)
+                .map(|(key, _)| key)
+                .collect();
+            assert_eq!(actual, expected);
+            assert_eq!(
+                trie.longest_prefix_match(&query).map(|(key, _)| key),
+                expected.last().copied()
+            );
+
+            // lower_bound() is the number of smaller keys.
+            assert_eq!(
+                trie.lower_bound(&query),
+                sorted.partition_point(|key| **key < query) as u64
+            );
+        }
+
+        // range() agrees with BTreeSet::range().
+        let to_bound = |bound: u8, key: &Vec<u8>| match bound {
+            0 => Bound::Included(key.clone()),
+            1 => Bound::Excluded(key.clone()),
+            _ => Bound::Unbounded,
+        };
+        for (a, b) in generate_keys(&mut rng, 20)
+            .iter()
+            .zip(generate_keys(&mut rng, 20).iter())
+        {
+            let (start, end) = (
+                to_bound(rng.gen_range(0..3), a),
+                to_bound(rng.gen_range(0..3), b),
+            );
+            if let (
+                Bound::Included(a) | Bound::Excluded(a),
+                Bound::Included(b) | Bound::Excluded(b),
+            ) = (&start, &end)
+            {
+                // BTreeSet::range() panics on these.
+                if a > b
+                    || (a == b
+                        && matches!((&start, &end), (Bound::Excluded(_), Bound::Excluded(_))))
+                {
+                    continue;
+                }
+            }
+            let expected: Vec<&Vec<u8>> = set.range((start.clone(), end.clone())).collect();
+            let bounds = (
+                start.as_ref().map(|key| &key[..]),
+                end.as_ref().map(|key| &key[..]),
+            );
+            let actual: Vec<Vec<u8>> = trie.range(bounds).map(|(key, _)| key).collect();
+            assert_eq!(actual.iter().collect::<Vec<_>>(), expected);
+        }
+    }
+}
+
+#[test]
+fn louds_patricia_trie_fuzzing_test() {
+    use std::collections::BTreeSet;
+
+    let samples = 100;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..samples {
+        let keys = generate_keys(&mut rng, 100);
+        eprintln!("from_sorted_keys(): keys = {:?}", keys);
+
+        let trie = LoudsPatriciaTrie::from_sorted_keys(&keys).unwrap();
+        let set: BTreeSet<Vec<u8>> = keys.iter().cloned().collect();
+        assert_eq!(trie.num_keys(), set.len() as u64);
+
+        // Labels from root to the node spell the key.
+        for key in &set {
+            let node_num = trie.get_node(key).unwrap();
+            let mut spelled: Vec<u8> = Vec::new();
+            for node_num in trie.louds().child_to_ancestors(node_num) {
+                spelled.splice(0..0, trie.label(node_num).iter().copied());
+            }
+            assert_eq!(&spelled, key);
+        }
+
+        // Every node but root branches or ends a key.
+        for node_num in 2..=trie.louds().num_nodes() {
+            let node_num = LoudsNodeNum(node_num);
+            assert!(!trie.label(node_num).is_empty());
+            assert!(
+                trie.is_terminal(node_num)
+                    || trie.louds().parent_to_children_indices(node_num).len() >= 2
+            );
+        }
+
+        for query in generate_keys(&mut rng, 100) {
+            assert_eq!(trie.contains(&query), set.contains(&query));
+
+            let expected: Vec<&Vec<u8>> =
+                set.iter().filter(|key| key.starts_with(&query)).collect();
+            let actual: Vec<Vec<u8>> = trie.predictive_search(&query).map(|(key, _)| key).collect();
+            assert_eq!(actual.iter().collect::<Vec<_>>(), expected);
+        }
+    }
+}
+
+#[test]
+fn fuzzy_search_fuzzing_test() {
+    use rand::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn levenshtein(a: &[u8], b: &[u8]) -> usize {
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, x) in a.iter().enumerate() {
+            let mut prev = row[0];
+            row[0] = i + 1;
+            for (j, y) in b.iter().enumerate() {
+                let substitution = prev + (x != y) as usize;
+                prev = row[j + 1];
+                row[j + 1] = substitution.min(row[j + 1] + 1).min(row[j] + 1);
+            }
+        }
+        row[b.len()]
+    }
+
+    let samples = 100;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..samples {
+        let keys = generate_keys(&mut rng, 100);
+        let trie = build_trie(&mut rng, &keys);
+        let set: BTreeSet<Vec<u8>> = keys.into_iter().collect();
+
+        for query in generate_keys(&mut rng, 10) {
+            let max_edits = rng.gen_range(0..4);
+            let expected: Vec<(Vec<u8>, usize)> = set
+                .iter()
+                .map(|key| (key.clone(), levenshtein(key, &query)))
+                .filter(|&(_, distance)| distance <= max_edits)
+                .collect();
+            let actual: Vec<(Vec<u8>, usize)> = trie
+                .fuzzy_search(&query, max_edits)
+                .map(|(key, _, distance)| (key, distance))
+                .collect();
+            assert_eq!(actual, expected);
+        }
+    }
+}
+
+#[test]
+fn automaton_search_fuzzing_test() {
+    use rand::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn run<A: Automaton<u8>>(automaton: &A, key: &[u8]) -> bool {
+        let state = key.iter().fold(automaton.start(), |state, label| {
+            automaton.accept(&state, label)
+        });
+        automaton.is_match(&state)
+    }
+
+    fn check<A: Automaton<u8>>(trie: &LoudsTrie<u8>, set: &BTreeSet<Vec<u8>>, automaton: &A) {
+        let expected: Vec<&Vec<u8>> = set.iter().filter(|key| run(automaton, key)).collect();
+        let actual: Vec<Vec<u8>> = trie.search(automaton).map(|(key, _)| key).collect();
+        assert_eq!(actual.iter().collect::<Vec<_>>(), expected);
+    }
+
+    let samples = 100;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..samples {
+        let keys = generate_keys(&mut rng, 100);
+        let trie = build_trie(&mut rng, &keys);
+        let set: BTreeSet<Vec<u8>> = keys.into_iter().collect();
+
+        for query in generate_keys(&mut rng, 10) {
+            check(&trie, &set, &Prefix::new(&query));
+            check(&trie, &set, &Subsequence::new(&query));
+
+            // Glob from the query, with some labels replaced by wildcards and classes.
+            let pattern: String = query
+                .iter()
+                .map(|&label| match rng.gen_range(0..6) {
+                    0 => String::from("*"),
+                    1 => String::from("?"),
+                    2 => String::from("[a-b]"),
+                    3 => String::from("[!c]"),
+                    _ => (label as char).to_string(),
+                })
+                .collect();
+            eprintln!("Glob::new(\"{}\")", pattern);
+            check(&trie, &set, &Glob::new(&pattern).unwrap());
+        }
+    }
+}
+
+#[test]
+fn louds_map_fuzzing_test() {
+    use rand::prelude::*;
+    use std::collections::BTreeMap;
+
+    let samples = 100;
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..samples {
+        let btree_map: BTreeMap<Vec<u8>, u32> = generate_keys(&mut rng, 100)
+            .into_iter()
+            .map(|key| (key, rng.gen()))
+            .collect();
+        eprintln!("from_sorted_iter(): entries = {:?}", btree_map);
+
+        let map = LoudsMap::from_sorted_iter(btree_map.iter().map(|(k, v)| (k, *v))).unwrap();
+        assert_eq!(map.len(), btree_map.len());
+        assert!(map.iter().map(|(k, v)| (k, *v)).eq(btree_map.clone()));
+
+        for query in generate_keys(&mut rng, 100) {
+            assert_eq!(map.get(&query), btree_map.get(&query));
+            assert!(map.get_by_prefix(&query).map(|(k, v)| (k, *v)).eq(btree_map
+                .iter()
+                .filter(|(k, _)| k.starts_with(&query))
+                .map(|(k, v)| (k.clone(), *v))));
         }
     }
 }